@@ -1,6 +1,7 @@
 //! Configuration types for customizing JSON parsing behavior.
 
 use crate::misc::Sealed;
+use core::ops::{BitOr, BitOrAssign};
 
 /// Configuration trait for JSON parsing behavior.
 pub trait Config: Sealed {
@@ -10,31 +11,305 @@ pub trait Config: Sealed {
     #[doc(hidden)]
     fn trailing_comma(&self) -> bool;
 
+    #[doc(hidden)]
+    fn max_depth(&self) -> usize;
+
+    #[doc(hidden)]
+    fn max_string_len(&self) -> usize;
+
+    #[doc(hidden)]
+    fn max_container_len(&self) -> usize;
+
+    #[doc(hidden)]
+    fn max_alloc(&self) -> usize;
+
+    #[doc(hidden)]
+    fn duplicate_keys(&self) -> DuplicateKeys;
+
+    #[doc(hidden)]
+    fn error_path(&self) -> bool;
+
+    #[doc(hidden)]
+    fn dialect(&self) -> Dialect;
+
     #[doc(hidden)]
     #[cfg(feature = "comment")]
-    fn comments(&self) -> bool;
+    fn comments(&self) -> CommentSyntax;
+}
+
+/// Policy for handling duplicate keys in an object.
+///
+/// With serde, the keys seen so far are kept in a set, which requires the `alloc` feature.
+/// Values built by the parser look keys up through their own [`ObjectBuilder::find`], which
+/// for [`Object`](crate::value::Object) scans the previous entries unless the `hash-index`
+/// feature is enabled. The unchecked parsing methods do not apply it.
+///
+/// [`ObjectBuilder::find`]: crate::value::builder::ObjectBuilder::find
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeys {
+    /// Keeps every entry as is.
+    #[default]
+    Allow,
+
+    /// Keeps the first entry and ignores the later ones.
+    FirstWins,
+
+    /// Keeps the position of the first entry but takes the value of the last one.
+    ///
+    /// With serde, the entry is visited at the position of the last one instead. The object
+    /// is looked ahead once to find the superseded entries, so a [`Reader`](crate::source::Reader)
+    /// buffers the whole object in memory before visiting its first entry.
+    LastWins,
+
+    /// Results in [`DuplicateKey`](crate::ErrorKind::DuplicateKey) error.
+    Reject,
+}
+
+impl DuplicateKeys {
+    #[inline(always)]
+    const fn from_u8(v: u8) -> Self {
+        match v {
+            1 => Self::FirstWins,
+            2 => Self::LastWins,
+            3 => Self::Reject,
+            _ => Self::Allow,
+        }
+    }
+}
+
+/// Set of extensions to the JSON syntax, mostly the ones from [JSON5](https://spec.json5.org).
+///
+/// Flags can be combined with `|`. Comments and trailing commas are configured separately.
+/// The unchecked parsing methods and lazy values do not support any of them.
+///
+/// # Example
+/// ```
+/// use flexon::{Parser, Value, config::{Dialect, RTConfig}};
+///
+/// let cfg = RTConfig::new().dialect(Dialect::UNQUOTED_KEYS | Dialect::HEX_NUMBERS);
+/// let val: Value = Parser::new_with("{mask: 0xFF}", cfg).parse()?;
+///
+/// assert_eq!(val["mask"].as_u64(), Some(255));
+/// # Ok::<(), flexon::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Dialect(u16);
+
+impl Dialect {
+    /// Plain JSON.
+    pub const NONE: Self = Self(0);
+
+    /// Object keys written as identifiers, e.g. `{key: 1}`.
+    ///
+    /// Identifiers start with a Unicode letter, `_` or `$`, followed by any of those along with
+    /// digits, combining marks, connector punctuations, ZWNJ and ZWJ, as in JSON5. Escape
+    /// sequences are not allowed in them.
+    pub const UNQUOTED_KEYS: Self = Self(1);
+
+    /// Strings enclosed in single quotes, e.g. `'value'`, along with the `\'` escape sequence.
+    pub const SINGLE_QUOTES: Self = Self(1 << 1);
+
+    /// Hexadecimal integers, e.g. `0xC0FFEE`.
+    pub const HEX_NUMBERS: Self = Self(1 << 2);
+
+    /// Numbers starting with a decimal point, e.g. `.5`.
+    pub const LEADING_DECIMAL_POINT: Self = Self(1 << 3);
+
+    /// Numbers ending with a decimal point, e.g. `5.`.
+    pub const TRAILING_DECIMAL_POINT: Self = Self(1 << 4);
+
+    /// Numbers with an explicit plus sign, e.g. `+1`.
+    pub const PLUS_SIGN: Self = Self(1 << 5);
+
+    /// `Infinity`, `-Infinity` and `NaN`, parsed as floats.
+    pub const NON_FINITE: Self = Self(1 << 6);
+
+    /// Strings continued on the next line by escaping the line terminator.
+    pub const MULTILINE_STRINGS: Self = Self(1 << 7);
+
+    /// The `\v`, `\0` and `\xHH` escape sequences.
+    pub const ESCAPES: Self = Self(1 << 8);
+
+    /// Whitespace beyond JSON's, that is `\v`, `\f`, the Unicode space separators, U+2028,
+    /// U+2029 and the byte order mark U+FEFF.
+    pub const WHITESPACE: Self = Self(1 << 9);
+
+    /// Every extension above, which together with comments and trailing commas makes up JSON5.
+    pub const JSON5: Self = Self((1 << 10) - 1);
+
+    /// Returns the raw bits, meant for [`CTConfig::dialect`].
+    #[inline]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Creates a dialect from the raw bits, unknown bits are ignored.
+    #[inline]
+    pub const fn from_bits(v: u16) -> Self {
+        Self(v & Self::JSON5.0)
+    }
+
+    /// Returns the flags of both, same as `|` but usable in const contexts.
+    #[inline]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns `true` if all the flags of `other` are set.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if any of the flags of `other` are set.
+    #[inline]
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Returns `true` if no flags are set.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for Dialect {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Dialect {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0
+    }
+}
+
+/// Set of comment syntaxes recognized when comments are allowed.
+///
+/// Flags can be combined with `|`. Every comment is recorded regardless of its syntax,
+/// see [`Parser::take_comments`](crate::Parser::take_comments).
+///
+/// # Example
+/// ```
+/// use flexon::{Parser, Value, config::{CommentSyntax, RTConfig}};
+///
+/// let cfg = RTConfig::new().comment_syntax(CommentSyntax::HASH);
+/// let mut parser = Parser::new_with("# legacy\n{\"port\": 80}", cfg);
+/// let val: Value = parser.parse()?;
+///
+/// assert_eq!(val["port"].as_u64(), Some(80));
+/// assert_eq!(parser.take_comments()[0].as_str(), " legacy");
+/// # Ok::<(), flexon::Error>(())
+/// ```
+#[cfg(feature = "comment")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CommentSyntax(u8);
+
+#[cfg(feature = "comment")]
+impl CommentSyntax {
+    /// No comments.
+    pub const NONE: Self = Self(0);
+
+    /// `//` line comments and `/* */` block comments, as in JSONC and JSON5.
+    pub const SLASH: Self = Self(1);
+
+    /// `#` line comments, as in HJSON and YAML.
+    pub const HASH: Self = Self(1 << 1);
+
+    /// Every syntax above.
+    pub const ALL: Self = Self((1 << 2) - 1);
+
+    /// Returns the raw bits, meant for [`CTConfig::comment_syntax`].
+    #[inline]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Creates a comment syntax from the raw bits, unknown bits are ignored.
+    #[inline]
+    pub const fn from_bits(v: u8) -> Self {
+        Self(v & Self::ALL.0)
+    }
+
+    /// Returns the flags of both, same as `|` but usable in const contexts.
+    #[inline]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns `true` if all the flags of `other` are set.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if no flags are set.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+#[cfg(feature = "comment")]
+impl BitOr for CommentSyntax {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+#[cfg(feature = "comment")]
+impl BitOrAssign for CommentSyntax {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0
+    }
 }
 
 /// Runtime configuration for JSON parsing behavior.
 ///
 /// Useful when you don't care about performance or want to reduce build size/time.
+#[derive(Clone)]
 pub struct RTConfig {
     comma: bool,
     trailing_comma: bool,
+    max_depth: usize,
+    max_string_len: usize,
+    max_container_len: usize,
+    max_alloc: usize,
+    duplicate_keys: DuplicateKeys,
+    error_path: bool,
+    dialect: Dialect,
     #[cfg(feature = "comment")]
-    comments: bool,
+    comments: CommentSyntax,
 }
 
 impl RTConfig {
     /// Creates a runtime configuration with default settings.
     ///
-    /// By default, commas are required and trailing commas are not allowed.
+    /// By default, commas are required, trailing commas are not allowed and there are no limits.
     pub fn new() -> Self {
         Self {
             comma: false,
             trailing_comma: false,
+            max_depth: usize::MAX,
+            max_string_len: usize::MAX,
+            max_container_len: usize::MAX,
+            max_alloc: usize::MAX,
+            duplicate_keys: DuplicateKeys::Allow,
+            error_path: false,
+            dialect: Dialect::NONE,
             #[cfg(feature = "comment")]
-            comments: false,
+            comments: CommentSyntax::NONE,
         }
     }
 
@@ -56,9 +331,164 @@ impl RTConfig {
         self
     }
 
-    /// Sets whether comments are allowed or not.
+    /// Sets the maximum nesting depth of arrays and objects.
+    ///
+    /// Exceeding it results in [`DepthLimitExceeded`](crate::ErrorKind::DepthLimitExceeded) error.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{ErrorKind, Parser, Value, config::RTConfig};
+    ///
+    /// let mut parser = Parser::new_with("[[[]]]", RTConfig::new().max_depth(2));
+    /// let res: Result<Value, _> = parser.parse();
+    ///
+    /// assert_eq!(res.unwrap_err(), ErrorKind::DepthLimitExceeded);
+    /// ```
+    pub fn max_depth(mut self, v: usize) -> Self {
+        self.max_depth = v;
+        self
+    }
+
+    /// Sets the maximum length of a string in bytes, as it appears in the source.
+    ///
+    /// Exceeding it results in [`StringTooLong`](crate::ErrorKind::StringTooLong) error.
+    pub fn max_string_len(mut self, v: usize) -> Self {
+        self.max_string_len = v;
+        self
+    }
+
+    /// Sets the maximum number of elements in an array or entries in an object.
+    ///
+    /// Exceeding it results in [`ContainerTooLarge`](crate::ErrorKind::ContainerTooLarge) error.
+    pub fn max_container_len(mut self, v: usize) -> Self {
+        self.max_container_len = v;
+        self
+    }
+
+    /// Sets the maximum number of bytes allocated while building a value.
+    ///
+    /// Exceeding it results in [`AllocLimitExceeded`](crate::ErrorKind::AllocLimitExceeded) error.
+    ///
+    /// This is an estimate rather than the exact amount. Strings account for their length
+    /// and each array element or object entry accounts for the size of the value type. With
+    /// serde, only strings are accounted as the size of the deserialized types is unknown.
+    ///
+    /// The budget is shared across a single parser, except when iterating over [`Stream`](crate::Stream)
+    /// and similar, where it is reset for every yielded value.
+    pub fn max_alloc(mut self, v: usize) -> Self {
+        self.max_alloc = v;
+        self
+    }
+
+    /// Sets how duplicate keys in an object are handled.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{Parser, Value, config::{DuplicateKeys, RTConfig}};
+    ///
+    /// let cfg = RTConfig::new().duplicate_keys(DuplicateKeys::LastWins);
+    /// let val: Value = Parser::new_with(r#"{"a": 1, "b": 2, "a": 3}"#, cfg).parse()?;
+    /// let obj = val.as_object().unwrap();
+    ///
+    /// assert_eq!(obj.len(), 2);
+    /// assert_eq!(obj["a"].as_u64(), Some(3));
+    /// # Ok::<(), flexon::Error>(())
+    /// ```
+    pub fn duplicate_keys(mut self, v: DuplicateKeys) -> Self {
+        self.duplicate_keys = v;
+        self
+    }
+
+    /// Sets whether serde errors carry the JSON pointer path of where they occurred.
+    ///
+    /// Tracking the path costs a little for every key, so it is disabled by default.
+    /// Requires the `alloc` feature, otherwise it has no effect.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "serde", feature = "alloc"))] {
+    /// use flexon::{Parser, config::RTConfig};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Item {
+    ///     price: u32,
+    /// }
+    ///
+    /// let src = r#"{"items": [{"price": 1}, {"price": "2"}]}"#;
+    /// let mut parser = Parser::new_with(src, RTConfig::new().error_path(true));
+    /// let err = <std::collections::HashMap<String, Vec<Item>>>::deserialize(&mut parser).unwrap_err();
+    ///
+    /// assert_eq!(err.path(), Some("/items/1/price"));
+    /// # }
+    /// ```
+    pub fn error_path(mut self, v: bool) -> Self {
+        self.error_path = v;
+        self
+    }
+
+    /// Sets the extensions to the JSON syntax that are allowed. See [`Dialect`].
+    pub fn dialect(mut self, v: Dialect) -> Self {
+        self.dialect = v;
+        self
+    }
+
+    /// Sets whether `Infinity`, `-Infinity` and `NaN` are parsed as floats, leaving the rest
+    /// of the dialect as is. See [`Dialect::NON_FINITE`].
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{Parser, Value, config::RTConfig};
+    ///
+    /// let val: Value = Parser::new_with("[NaN, -Infinity]", RTConfig::new().allow_non_finite(true)).parse()?;
+    ///
+    /// assert!(val[0].as_f64().unwrap().is_nan());
+    /// assert_eq!(val[1].as_f64(), Some(f64::NEG_INFINITY));
+    /// # Ok::<(), flexon::Error>(())
+    /// ```
+    pub fn allow_non_finite(mut self, v: bool) -> Self {
+        self.dialect.0 = match v {
+            true => self.dialect.0 | Dialect::NON_FINITE.0,
+            _ => self.dialect.0 & !Dialect::NON_FINITE.0,
+        };
+        self
+    }
+
+    /// Allows JSON5, that is every extension in [`Dialect::JSON5`] along with trailing commas,
+    /// and comments when the `comment` feature is enabled.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{Parser, Value, config::RTConfig};
+    ///
+    /// let src = "{name: 'flexon', version: +0.4, flags: [0x1, .5, Infinity,],}";
+    /// let val: Value = Parser::new_with(src, RTConfig::new().json5()).parse()?;
+    ///
+    /// assert_eq!(val["name"].as_str(), Some("flexon"));
+    /// assert_eq!(val["flags"][1].as_f64(), Some(0.5));
+    /// # Ok::<(), flexon::Error>(())
+    /// ```
+    pub fn json5(mut self) -> Self {
+        self.dialect = Dialect::JSON5;
+        self.trailing_comma = true;
+        #[cfg(feature = "comment")]
+        (self.comments = CommentSyntax::SLASH);
+        self
+    }
+
+    /// Sets whether `//` and `/* */` comments are allowed or not.
     #[cfg(feature = "comment")]
     pub fn allow_comments(mut self, v: bool) -> Self {
+        self.comments = match v {
+            true => CommentSyntax::SLASH,
+            _ => CommentSyntax::NONE,
+        };
+        self
+    }
+
+    /// Sets the comment syntaxes that are allowed. See [`CommentSyntax`].
+    #[cfg(feature = "comment")]
+    pub fn comment_syntax(mut self, v: CommentSyntax) -> Self {
         self.comments = v;
         self
     }
@@ -75,9 +505,44 @@ impl Config for RTConfig {
         self.trailing_comma
     }
 
+    #[inline(always)]
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    #[inline(always)]
+    fn max_string_len(&self) -> usize {
+        self.max_string_len
+    }
+
+    #[inline(always)]
+    fn max_container_len(&self) -> usize {
+        self.max_container_len
+    }
+
+    #[inline(always)]
+    fn max_alloc(&self) -> usize {
+        self.max_alloc
+    }
+
+    #[inline(always)]
+    fn duplicate_keys(&self) -> DuplicateKeys {
+        self.duplicate_keys
+    }
+
+    #[inline(always)]
+    fn error_path(&self) -> bool {
+        self.error_path
+    }
+
+    #[inline(always)]
+    fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
     #[inline(always)]
     #[cfg(feature = "comment")]
-    fn comments(&self) -> bool {
+    fn comments(&self) -> CommentSyntax {
         self.comments
     }
 }
@@ -85,53 +550,265 @@ impl Config for RTConfig {
 impl Sealed for RTConfig {}
 
 /// Compile-time configuration for JSON parsing behavior.
+///
+/// New parameters are only ever appended, so explicit annotations such as
+/// `CTConfig<false, true>` keep naming the same settings.
+#[derive(Clone, Copy)]
 pub struct CTConfig<
     const COMMA: bool = true,
     const TRAILING_COMMA: bool = false,
     #[cfg(feature = "comment")] const COMMENTS: bool = false,
+    const MAX_DEPTH: usize = { usize::MAX },
+    const MAX_STRING_LEN: usize = { usize::MAX },
+    const MAX_CONTAINER_LEN: usize = { usize::MAX },
+    const MAX_ALLOC: usize = { usize::MAX },
+    const DUPLICATE_KEYS: u8 = 0,
+    const ERROR_PATH: bool = false,
+    const DIALECT: u16 = 0,
+    #[cfg(feature = "comment")] const COMMENT_SYNTAX: u8 = 0,
 >;
 
 impl CTConfig {
     /// Creates a compile-time configuration with default settings.
     ///
-    /// By default, commas are required and both trailing commas and comments are not allowed.
+    /// By default, commas are required, both trailing commas and comments
+    /// are not allowed and there are no limits.
     pub fn new() -> Self {
         Self
     }
 }
 
-impl<const A: bool, const B: bool> CTConfig<A, B> {
-    /// Allows comments when parsing.
-    #[inline]
-    #[cfg(feature = "comment")]
-    pub fn allow_comments(self) -> CTConfig<A, B, true> {
-        CTConfig
-    }
-}
-
 #[cfg(feature = "comment")]
 // #[doc(cfg(all(not(feature = "comment"), feature = "comment")))]
 mod __ {
-    use super::{CTConfig, Config, Sealed};
+    use super::{CTConfig, CommentSyntax, Config, Dialect, DuplicateKeys, Sealed};
 
-    impl<const A: bool, const B: bool> CTConfig<true, A, B> {
+    impl<
+        const B: bool,
+        const C: bool,
+        const D: usize,
+        const L: usize,
+        const N: usize,
+        const M: usize,
+        const K: u8,
+        const P: bool,
+        const X: u16,
+        const S: u8,
+    > CTConfig<true, B, C, D, L, N, M, K, P, X, S>
+    {
         /// Makes commas optional. As a side effect trailing commas are allowed automatically.
         #[inline]
-        pub fn optional_comma(self) -> CTConfig<false, true, B> {
+        pub fn optional_comma(self) -> CTConfig<false, true, C, D, L, N, M, K, P, X, S> {
             CTConfig
         }
     }
 
-    impl<const A: bool, const B: bool> CTConfig<A, false, B> {
+    impl<
+        const A: bool,
+        const C: bool,
+        const D: usize,
+        const L: usize,
+        const N: usize,
+        const M: usize,
+        const K: u8,
+        const P: bool,
+        const X: u16,
+        const S: u8,
+    > CTConfig<A, false, C, D, L, N, M, K, P, X, S>
+    {
         /// Allows trailing commas when parsing.
         #[inline]
-        pub fn allow_trailing_comma(self) -> CTConfig<A, true, B> {
+        pub fn allow_trailing_comma(self) -> CTConfig<A, true, C, D, L, N, M, K, P, X, S> {
+            CTConfig
+        }
+    }
+
+    impl<
+        const A: bool,
+        const B: bool,
+        const D: usize,
+        const L: usize,
+        const N: usize,
+        const M: usize,
+        const K: u8,
+        const P: bool,
+        const X: u16,
+        const S: u8,
+    > CTConfig<A, B, false, D, L, N, M, K, P, X, S>
+    {
+        /// Allows `//` and `/* */` comments when parsing.
+        #[inline]
+        pub fn allow_comments(self) -> CTConfig<A, B, true, D, L, N, M, K, P, X, S> {
             CTConfig
         }
     }
 
-    impl<const COMMA: bool, const TRAILING_COMMA: bool, const COMMENTS: bool> Config
-        for CTConfig<COMMA, TRAILING_COMMA, COMMENTS>
+    impl<
+        const A: bool,
+        const B: bool,
+        const C: bool,
+        const D: usize,
+        const L: usize,
+        const N: usize,
+        const M: usize,
+        const K: u8,
+        const P: bool,
+        const X: u16,
+        const S: u8,
+    > CTConfig<A, B, C, D, L, N, M, K, P, X, S>
+    {
+        /// Sets the maximum nesting depth of arrays and objects.
+        ///
+        /// Exceeding it results in [`DepthLimitExceeded`](crate::ErrorKind::DepthLimitExceeded) error.
+        #[inline]
+        pub fn max_depth<const V: usize>(self) -> CTConfig<A, B, C, V, L, N, M, K, P, X, S> {
+            CTConfig
+        }
+
+        /// Sets the maximum length of a string in bytes, as it appears in the source.
+        ///
+        /// Exceeding it results in [`StringTooLong`](crate::ErrorKind::StringTooLong) error.
+        #[inline]
+        pub fn max_string_len<const V: usize>(self) -> CTConfig<A, B, C, D, V, N, M, K, P, X, S> {
+            CTConfig
+        }
+
+        /// Sets the maximum number of elements in an array or entries in an object.
+        ///
+        /// Exceeding it results in [`ContainerTooLarge`](crate::ErrorKind::ContainerTooLarge) error.
+        #[inline]
+        pub fn max_container_len<const V: usize>(
+            self,
+        ) -> CTConfig<A, B, C, D, L, V, M, K, P, X, S> {
+            CTConfig
+        }
+
+        /// Sets the maximum number of bytes allocated while building a value.
+        ///
+        /// See [`RTConfig::max_alloc`](super::RTConfig::max_alloc) for how it is accounted. Exceeding it results
+        /// in [`AllocLimitExceeded`](crate::ErrorKind::AllocLimitExceeded) error.
+        #[inline]
+        pub fn max_alloc<const V: usize>(self) -> CTConfig<A, B, C, D, L, N, V, K, P, X, S> {
+            CTConfig
+        }
+
+        /// Keeps the first entry of duplicate keys in an object. See [`DuplicateKeys::FirstWins`].
+        #[inline]
+        pub fn first_key_wins(self) -> CTConfig<A, B, C, D, L, N, M, 1, P, X, S> {
+            CTConfig
+        }
+
+        /// Keeps the value of the last entry of duplicate keys in an object. See [`DuplicateKeys::LastWins`].
+        #[inline]
+        pub fn last_key_wins(self) -> CTConfig<A, B, C, D, L, N, M, 2, P, X, S> {
+            CTConfig
+        }
+
+        /// Rejects duplicate keys in an object. See [`DuplicateKeys::Reject`].
+        #[inline]
+        pub fn reject_duplicate_keys(self) -> CTConfig<A, B, C, D, L, N, M, 3, P, X, S> {
+            CTConfig
+        }
+
+        /// Makes serde errors carry the JSON pointer path of where they occurred. See [`RTConfig::error_path`](super::RTConfig::error_path).
+        #[inline]
+        pub fn error_path(self) -> CTConfig<A, B, C, D, L, N, M, K, true, X, S> {
+            CTConfig
+        }
+
+        /// Sets the extensions to the JSON syntax that are allowed, as the bits of a [`Dialect`].
+        ///
+        /// # Example
+        /// ```
+        /// use flexon::config::{CTConfig, Dialect};
+        ///
+        /// let cfg = CTConfig::new().dialect::<{ Dialect::SINGLE_QUOTES.union(Dialect::PLUS_SIGN).bits() }>();
+        /// ```
+        #[inline]
+        pub fn dialect<const V: u16>(self) -> CTConfig<A, B, C, D, L, N, M, K, P, V, S> {
+            const {
+                assert!(
+                    X != Dialect::NON_FINITE.bits() || V & Dialect::NON_FINITE.bits() != 0,
+                    "the dialect drops `allow_non_finite`, include `Dialect::NON_FINITE` in it instead"
+                )
+            }
+
+            CTConfig
+        }
+
+        /// Parses `Infinity`, `-Infinity` and `NaN` as floats. See [`Dialect::NON_FINITE`].
+        ///
+        /// The dialect is a single const parameter, so this can not be combined with other
+        /// extensions. Doing so is rejected at compile time, include [`Dialect::NON_FINITE`]
+        /// in the bits given to `dialect` instead.
+        ///
+        /// ```compile_fail
+        /// use flexon::config::{CTConfig, Dialect};
+        ///
+        /// let cfg = CTConfig::new().dialect::<{ Dialect::HEX_NUMBERS.bits() }>().allow_non_finite();
+        /// ```
+        #[inline]
+        pub fn allow_non_finite(
+            self,
+        ) -> CTConfig<A, B, C, D, L, N, M, K, P, { Dialect::NON_FINITE.bits() }, S> {
+            const {
+                assert!(
+                    X == 0 || X == Dialect::NON_FINITE.bits(),
+                    "`allow_non_finite` can not be combined with a dialect, include `Dialect::NON_FINITE` in it instead"
+                )
+            }
+
+            CTConfig
+        }
+
+        /// Allows JSON5, that is every extension in [`Dialect::JSON5`] along with trailing commas and comments.
+        #[inline]
+        pub fn json5(
+            self,
+        ) -> CTConfig<A, true, true, D, L, N, M, K, P, { Dialect::JSON5.bits() }, S> {
+            CTConfig
+        }
+
+        /// Sets the comment syntaxes that are allowed, as the bits of a [`CommentSyntax`].
+        ///
+        /// # Example
+        /// ```
+        /// use flexon::config::{CTConfig, CommentSyntax};
+        ///
+        /// let cfg = CTConfig::new().comment_syntax::<{ CommentSyntax::ALL.bits() }>();
+        /// ```
+        #[inline]
+        pub fn comment_syntax<const V: u8>(self) -> CTConfig<A, B, false, D, L, N, M, K, P, X, V> {
+            CTConfig
+        }
+    }
+
+    impl<
+        const COMMA: bool,
+        const TRAILING_COMMA: bool,
+        const COMMENTS: bool,
+        const MAX_DEPTH: usize,
+        const MAX_STRING_LEN: usize,
+        const MAX_CONTAINER_LEN: usize,
+        const MAX_ALLOC: usize,
+        const DUPLICATE_KEYS: u8,
+        const ERROR_PATH: bool,
+        const DIALECT: u16,
+        const COMMENT_SYNTAX: u8,
+    > Config
+        for CTConfig<
+            COMMA,
+            TRAILING_COMMA,
+            COMMENTS,
+            MAX_DEPTH,
+            MAX_STRING_LEN,
+            MAX_CONTAINER_LEN,
+            MAX_ALLOC,
+            DUPLICATE_KEYS,
+            ERROR_PATH,
+            DIALECT,
+            COMMENT_SYNTAX,
+        >
     {
         #[inline(always)]
         fn comma(&self) -> bool {
@@ -144,35 +821,254 @@ mod __ {
         }
 
         #[inline(always)]
-        fn comments(&self) -> bool {
-            COMMENTS
+        fn max_depth(&self) -> usize {
+            MAX_DEPTH
+        }
+
+        #[inline(always)]
+        fn max_string_len(&self) -> usize {
+            MAX_STRING_LEN
+        }
+
+        #[inline(always)]
+        fn max_container_len(&self) -> usize {
+            MAX_CONTAINER_LEN
+        }
+
+        #[inline(always)]
+        fn max_alloc(&self) -> usize {
+            MAX_ALLOC
+        }
+
+        #[inline(always)]
+        fn duplicate_keys(&self) -> DuplicateKeys {
+            const { DuplicateKeys::from_u8(DUPLICATE_KEYS) }
+        }
+
+        #[inline(always)]
+        fn error_path(&self) -> bool {
+            ERROR_PATH
+        }
+
+        #[inline(always)]
+        fn dialect(&self) -> Dialect {
+            const { Dialect::from_bits(DIALECT) }
+        }
+
+        #[inline(always)]
+        fn comments(&self) -> CommentSyntax {
+            const {
+                match COMMENTS {
+                    true => CommentSyntax::SLASH.union(CommentSyntax::from_bits(COMMENT_SYNTAX)),
+                    _ => CommentSyntax::from_bits(COMMENT_SYNTAX),
+                }
+            }
         }
     }
 
-    impl<const A: bool, const B: bool, const C: bool> Sealed for CTConfig<A, B, C> {}
+    impl<
+        const A: bool,
+        const B: bool,
+        const C: bool,
+        const D: usize,
+        const L: usize,
+        const N: usize,
+        const M: usize,
+        const K: u8,
+        const P: bool,
+        const X: u16,
+        const S: u8,
+    > Sealed for CTConfig<A, B, C, D, L, N, M, K, P, X, S>
+    {
+    }
 }
 
 #[cfg(not(feature = "comment"))]
 mod __ {
-    use super::{CTConfig, Config, Sealed};
+    use super::{CTConfig, Config, Dialect, DuplicateKeys, Sealed};
 
-    impl<const A: bool> CTConfig<true, A> {
+    impl<
+        const B: bool,
+        const D: usize,
+        const L: usize,
+        const N: usize,
+        const M: usize,
+        const K: u8,
+        const P: bool,
+        const X: u16,
+    > CTConfig<true, B, D, L, N, M, K, P, X>
+    {
         /// Makes commas optional. As a side effect trailing commas are allowed automatically.
         #[inline]
-        pub fn optional_comma(self) -> CTConfig<false, true> {
+        pub fn optional_comma(self) -> CTConfig<false, true, D, L, N, M, K, P, X> {
             CTConfig
         }
     }
 
-    impl<const V: bool> CTConfig<V> {
+    impl<
+        const A: bool,
+        const D: usize,
+        const L: usize,
+        const N: usize,
+        const M: usize,
+        const K: u8,
+        const P: bool,
+        const X: u16,
+    > CTConfig<A, false, D, L, N, M, K, P, X>
+    {
         /// Allows trailing commas when parsing.
         #[inline]
-        pub fn allow_trailing_comma(self) -> CTConfig<V, true> {
+        pub fn allow_trailing_comma(self) -> CTConfig<A, true, D, L, N, M, K, P, X> {
             CTConfig
         }
     }
 
-    impl<const COMMA: bool, const TRAILING_COMMA: bool> Config for CTConfig<COMMA, TRAILING_COMMA> {
+    impl<
+        const A: bool,
+        const B: bool,
+        const D: usize,
+        const L: usize,
+        const N: usize,
+        const M: usize,
+        const K: u8,
+        const P: bool,
+        const X: u16,
+    > CTConfig<A, B, D, L, N, M, K, P, X>
+    {
+        /// Sets the maximum nesting depth of arrays and objects.
+        ///
+        /// Exceeding it results in [`DepthLimitExceeded`](crate::ErrorKind::DepthLimitExceeded) error.
+        #[inline]
+        pub fn max_depth<const V: usize>(self) -> CTConfig<A, B, V, L, N, M, K, P, X> {
+            CTConfig
+        }
+
+        /// Sets the maximum length of a string in bytes, as it appears in the source.
+        ///
+        /// Exceeding it results in [`StringTooLong`](crate::ErrorKind::StringTooLong) error.
+        #[inline]
+        pub fn max_string_len<const V: usize>(self) -> CTConfig<A, B, D, V, N, M, K, P, X> {
+            CTConfig
+        }
+
+        /// Sets the maximum number of elements in an array or entries in an object.
+        ///
+        /// Exceeding it results in [`ContainerTooLarge`](crate::ErrorKind::ContainerTooLarge) error.
+        #[inline]
+        pub fn max_container_len<const V: usize>(self) -> CTConfig<A, B, D, L, V, M, K, P, X> {
+            CTConfig
+        }
+
+        /// Sets the maximum number of bytes allocated while building a value.
+        ///
+        /// See [`RTConfig::max_alloc`](super::RTConfig::max_alloc) for how it is accounted. Exceeding it results
+        /// in [`AllocLimitExceeded`](crate::ErrorKind::AllocLimitExceeded) error.
+        #[inline]
+        pub fn max_alloc<const V: usize>(self) -> CTConfig<A, B, D, L, N, V, K, P, X> {
+            CTConfig
+        }
+
+        /// Keeps the first entry of duplicate keys in an object. See [`DuplicateKeys::FirstWins`].
+        #[inline]
+        pub fn first_key_wins(self) -> CTConfig<A, B, D, L, N, M, 1, P, X> {
+            CTConfig
+        }
+
+        /// Keeps the value of the last entry of duplicate keys in an object. See [`DuplicateKeys::LastWins`].
+        #[inline]
+        pub fn last_key_wins(self) -> CTConfig<A, B, D, L, N, M, 2, P, X> {
+            CTConfig
+        }
+
+        /// Rejects duplicate keys in an object. See [`DuplicateKeys::Reject`].
+        #[inline]
+        pub fn reject_duplicate_keys(self) -> CTConfig<A, B, D, L, N, M, 3, P, X> {
+            CTConfig
+        }
+
+        /// Makes serde errors carry the JSON pointer path of where they occurred. See [`RTConfig::error_path`](super::RTConfig::error_path).
+        #[inline]
+        pub fn error_path(self) -> CTConfig<A, B, D, L, N, M, K, true, X> {
+            CTConfig
+        }
+
+        /// Sets the extensions to the JSON syntax that are allowed, as the bits of a [`Dialect`].
+        ///
+        /// # Example
+        /// ```
+        /// use flexon::config::{CTConfig, Dialect};
+        ///
+        /// let cfg = CTConfig::new().dialect::<{ Dialect::SINGLE_QUOTES.union(Dialect::PLUS_SIGN).bits() }>();
+        /// ```
+        #[inline]
+        pub fn dialect<const V: u16>(self) -> CTConfig<A, B, D, L, N, M, K, P, V> {
+            const {
+                assert!(
+                    X != Dialect::NON_FINITE.bits() || V & Dialect::NON_FINITE.bits() != 0,
+                    "the dialect drops `allow_non_finite`, include `Dialect::NON_FINITE` in it instead"
+                )
+            }
+
+            CTConfig
+        }
+
+        /// Parses `Infinity`, `-Infinity` and `NaN` as floats. See [`Dialect::NON_FINITE`].
+        ///
+        /// The dialect is a single const parameter, so this can not be combined with other
+        /// extensions. Doing so is rejected at compile time, include [`Dialect::NON_FINITE`]
+        /// in the bits given to `dialect` instead.
+        ///
+        /// ```compile_fail
+        /// use flexon::config::{CTConfig, Dialect};
+        ///
+        /// let cfg = CTConfig::new().dialect::<{ Dialect::HEX_NUMBERS.bits() }>().allow_non_finite();
+        /// ```
+        #[inline]
+        pub fn allow_non_finite(
+            self,
+        ) -> CTConfig<A, B, D, L, N, M, K, P, { Dialect::NON_FINITE.bits() }> {
+            const {
+                assert!(
+                    X == 0 || X == Dialect::NON_FINITE.bits(),
+                    "`allow_non_finite` can not be combined with a dialect, include `Dialect::NON_FINITE` in it instead"
+                )
+            }
+
+            CTConfig
+        }
+
+        /// Allows JSON5, that is every extension in [`Dialect::JSON5`] along with trailing commas.
+        ///
+        /// Comments require the `comment` feature.
+        #[inline]
+        pub fn json5(self) -> CTConfig<A, true, D, L, N, M, K, P, { Dialect::JSON5.bits() }> {
+            CTConfig
+        }
+    }
+
+    impl<
+        const COMMA: bool,
+        const TRAILING_COMMA: bool,
+        const MAX_DEPTH: usize,
+        const MAX_STRING_LEN: usize,
+        const MAX_CONTAINER_LEN: usize,
+        const MAX_ALLOC: usize,
+        const DUPLICATE_KEYS: u8,
+        const ERROR_PATH: bool,
+        const DIALECT: u16,
+    > Config
+        for CTConfig<
+            COMMA,
+            TRAILING_COMMA,
+            MAX_DEPTH,
+            MAX_STRING_LEN,
+            MAX_CONTAINER_LEN,
+            MAX_ALLOC,
+            DUPLICATE_KEYS,
+            ERROR_PATH,
+            DIALECT,
+        >
+    {
         #[inline(always)]
         fn comma(&self) -> bool {
             !COMMA
@@ -182,7 +1078,54 @@ mod __ {
         fn trailing_comma(&self) -> bool {
             TRAILING_COMMA | !COMMA
         }
+
+        #[inline(always)]
+        fn max_depth(&self) -> usize {
+            MAX_DEPTH
+        }
+
+        #[inline(always)]
+        fn max_string_len(&self) -> usize {
+            MAX_STRING_LEN
+        }
+
+        #[inline(always)]
+        fn max_container_len(&self) -> usize {
+            MAX_CONTAINER_LEN
+        }
+
+        #[inline(always)]
+        fn max_alloc(&self) -> usize {
+            MAX_ALLOC
+        }
+
+        #[inline(always)]
+        fn duplicate_keys(&self) -> DuplicateKeys {
+            const { DuplicateKeys::from_u8(DUPLICATE_KEYS) }
+        }
+
+        #[inline(always)]
+        fn error_path(&self) -> bool {
+            ERROR_PATH
+        }
+
+        #[inline(always)]
+        fn dialect(&self) -> Dialect {
+            const { Dialect::from_bits(DIALECT) }
+        }
     }
 
-    impl<const A: bool, const B: bool> Sealed for CTConfig<A, B> {}
+    impl<
+        const A: bool,
+        const B: bool,
+        const D: usize,
+        const L: usize,
+        const N: usize,
+        const M: usize,
+        const K: u8,
+        const P: bool,
+        const X: u16,
+    > Sealed for CTConfig<A, B, D, L, N, M, K, P, X>
+    {
+    }
 }
@@ -0,0 +1,842 @@
+//! Format preserving editing of JSON documents, such as JSONC config files.
+
+#[cfg(feature = "comment")]
+use crate::config::CommentSyntax;
+use crate::{
+    Error, Parser,
+    config::{Config, RTConfig},
+    pointer::JsonPointer,
+    span::{GenericValue, Span, Value},
+    value::builder::ErrorBuilder,
+};
+use alloc::{format, string::String, vec, vec::Vec};
+use core::{
+    fmt::{self, Display, Formatter, Write},
+    ops::{Deref, Range},
+};
+
+/// A JSON document that can be edited while preserving its comments, whitespace and key order.
+///
+/// The document is kept as its source text along with the layout of its values, which is
+/// updated in place by every edit rather than parsing the whole source again. Edits only touch
+/// the bytes of the affected member along with the commas around it, and new members follow
+/// the layout and line endings of their siblings.
+///
+/// # Example
+/// ```
+/// use flexon::cst::Document;
+///
+/// let mut doc = Document::parse("{\n\t\"port\" : 80,\n\t\"debug\" : true,\n}")?;
+///
+/// doc.set(["port"], "8080")?;
+/// doc.remove(["debug"])?;
+/// doc.set(["hosts"], r#"["a", "b"]"#)?;
+///
+/// assert_eq!(doc.get(["port"]), Some("8080"));
+/// assert_eq!(doc.as_str(), "{\n\t\"port\" : 8080,\n\t\"hosts\" : [\"a\", \"b\"],\n}");
+/// # Ok::<(), flexon::cst::EditError>(())
+/// ```
+pub struct Document<C: Config = RTConfig> {
+    src: String,
+    root: Node,
+    cfg: C,
+}
+
+/// Represents error occurred while editing a [`Document`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum EditError {
+    /// The document or the given value is not valid under the configuration of the document.
+    ///
+    /// The span of the error is relative to whichever of them failed to parse.
+    Invalid(Span<Error>),
+
+    /// The path does not lead to a value, or to a place where one can be added.
+    NotFound,
+}
+
+impl Display for EditError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(err) => Display::fmt(err.data(), f),
+            Self::NotFound => f.write_str("path not found"),
+        }
+    }
+}
+
+impl core::error::Error for EditError {}
+
+enum Op<'v> {
+    Set(&'v str),
+    Insert(&'v str),
+    Remove,
+}
+
+impl Document {
+    /// Parses the given source as JSONC, that is with trailing commas and, when the
+    /// `comment` feature is enabled, comments allowed.
+    pub fn parse(src: impl Into<String>) -> Result<Self, EditError> {
+        let cfg = RTConfig::new().allow_trailing_comma(true);
+        #[cfg(feature = "comment")]
+        let cfg = cfg.allow_comments(true);
+
+        Self::parse_with(src, cfg)
+    }
+}
+
+impl<C: Config + Clone> Document<C> {
+    /// Parses the given source with the given configuration, which is used for every later edit as well.
+    pub fn parse_with(src: impl Into<String>, cfg: C) -> Result<Self, EditError> {
+        let src = src.into();
+        let root = Node::new(&parse(&src, cfg.clone())?);
+        Ok(Self { src, root, cfg })
+    }
+
+    /// Returns the source text of the value at the given path.
+    pub fn get<P>(&self, p: P) -> Option<&str>
+    where
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        let mut node = &self.root;
+        for seg in p {
+            node = node.child(node.find(&seg)?);
+        }
+
+        Some(&self.src[node.span.clone()])
+    }
+
+    /// Replaces the value at the given path with the given JSON text.
+    ///
+    /// Missing object members are added after the last member, as are array elements
+    /// when the index is the length of the array.
+    pub fn set<P>(&mut self, p: P, val: &str) -> Result<(), EditError>
+    where
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        self.edit(p, Op::Set(val))
+    }
+
+    /// Inserts the given JSON text at the given path.
+    ///
+    /// Array elements are inserted before the element at the index, shifting the rest. Object
+    /// members are handled the same as [`Document::set`].
+    pub fn insert<P>(&mut self, p: P, val: &str) -> Result<(), EditError>
+    where
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        self.edit(p, Op::Insert(val))
+    }
+
+    /// Removes the value at the given path, along with the comments on its lines.
+    ///
+    /// The root value can not be removed.
+    pub fn remove<P>(&mut self, p: P) -> Result<(), EditError>
+    where
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        self.edit(p, Op::Remove)
+    }
+
+    /// Returns the document as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.src
+    }
+
+    /// Consumes the document and returns its source text.
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.src
+    }
+
+    fn edit<P>(&mut self, p: P, op: Op) -> Result<(), EditError>
+    where
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        // only the new value is parsed, its layout is relative to its own text.
+        let (op, node) = match op {
+            Op::Set(v) => trim(v, self.cfg.clone()).map(|(v, n)| (Op::Set(v), Some(n)))?,
+            Op::Insert(v) => trim(v, self.cfg.clone()).map(|(v, n)| (Op::Insert(v), Some(n)))?,
+            Op::Remove => (Op::Remove, None),
+        };
+
+        let mut iter = p.into_iter();
+        let Some(mut seg) = iter.next() else {
+            return match (op, node) {
+                (Op::Set(v) | Op::Insert(v), Some(mut node)) => {
+                    let range = self.root.span.clone();
+                    node.shift(&(0..0), range.start);
+                    self.src.replace_range(range, v);
+                    self.root = node;
+                    Ok(())
+                }
+                _ => Err(EditError::NotFound),
+            };
+        };
+
+        let mut path = Vec::new();
+        let mut parent = &self.root;
+        for next in iter {
+            let idx = parent.find(&seg).ok_or(EditError::NotFound)?;
+            parent = parent.child(idx);
+            path.push(idx);
+            seg = next;
+        }
+
+        let Edit {
+            mut splices,
+            idx,
+            change,
+        } = Layout::new(&self.src, parent, Trivia::new(&self.cfg)).plan(&seg, op)?;
+
+        if let Change::Remove = change {
+            self.root.descend(&path).remove(idx);
+        }
+
+        // applied back to front so the offsets stay valid, while tracking where the
+        // edited item ends up.
+        let mut at = None;
+        splices.sort_by_key(|v| usize::MAX - v.range.start);
+        for Splice { range, text, item } in splices {
+            if let Some(v) = &mut at {
+                *v = *v - range.len() + text.len();
+            }
+
+            self.root.shift(&range, text.len());
+            self.src.replace_range(range.clone(), &text);
+            if let Some(v) = item {
+                at = Some(range.start + v);
+            }
+        }
+
+        let at = at.unwrap_or_default();
+
+        let parent = self.root.descend(&path);
+        match (change, node) {
+            (Change::Replace, Some(mut node)) => {
+                node.shift(&(0..0), at);
+                *parent.child_mut(idx) = node;
+            }
+            (Change::Add { key, val }, Some(mut node)) => {
+                node.shift(&(0..0), at + val);
+                match (&mut parent.kind, key) {
+                    (Kind::Object(members), Some((name, len))) => members.insert(
+                        idx,
+                        Member {
+                            name,
+                            key: at..at + len,
+                            val: node,
+                        },
+                    ),
+                    (Kind::Array(vals), _) => vals.insert(idx, node),
+                    _ => unreachable!(),
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: Config> Display for Document<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.src)
+    }
+}
+
+// layout of a parsed value, kept along with the source so that it is not parsed again.
+#[cfg_attr(test, derive(Debug, PartialEq))]
+struct Node {
+    span: Range<usize>,
+    kind: Kind,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+enum Kind {
+    Scalar,
+    Array(Vec<Node>),
+    Object(Vec<Member>),
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+struct Member {
+    name: String,
+    key: Range<usize>,
+    val: Node,
+}
+
+impl Node {
+    fn new<S: Deref<Target = str>>(v: &Span<GenericValue<S>>) -> Self {
+        let kind = match v.data() {
+            GenericValue::Object(obj) => Kind::Object(
+                obj.as_slice()
+                    .iter()
+                    .map(|(k, v)| Member {
+                        name: (**k.data()).into(),
+                        key: k.start()..k.end() + 1,
+                        val: Node::new(v),
+                    })
+                    .collect(),
+            ),
+            GenericValue::Array(arr) => Kind::Array(arr.iter().map(Node::new).collect()),
+            _ => Kind::Scalar,
+        };
+
+        Self {
+            span: v.start()..v.end() + 1,
+            kind,
+        }
+    }
+
+    fn find(&self, seg: &impl JsonPointer) -> Option<usize> {
+        match &self.kind {
+            Kind::Object(members) => {
+                let key = seg.as_key()?;
+                members.iter().position(|v| v.name == key)
+            }
+            Kind::Array(vals) => seg.as_index().filter(|&v| v < vals.len()),
+            _ => None,
+        }
+    }
+
+    fn child(&self, idx: usize) -> &Node {
+        match &self.kind {
+            Kind::Object(members) => &members[idx].val,
+            Kind::Array(vals) => &vals[idx],
+            _ => unreachable!(),
+        }
+    }
+
+    fn child_mut(&mut self, idx: usize) -> &mut Node {
+        match &mut self.kind {
+            Kind::Object(members) => &mut members[idx].val,
+            Kind::Array(vals) => &mut vals[idx],
+            _ => unreachable!(),
+        }
+    }
+
+    fn descend(&mut self, path: &[usize]) -> &mut Node {
+        path.iter().fold(self, |node, &idx| node.child_mut(idx))
+    }
+
+    fn remove(&mut self, idx: usize) {
+        match &mut self.kind {
+            Kind::Object(members) => _ = members.remove(idx),
+            Kind::Array(vals) => _ = vals.remove(idx),
+            _ => {}
+        }
+    }
+
+    // moves the offsets past the given range, which was replaced with `len` bytes.
+    fn shift(&mut self, range: &Range<usize>, len: usize) {
+        let start = |v: &mut usize| {
+            if *v >= range.end {
+                *v = *v - range.len() + len;
+            }
+        };
+        let end = |v: &mut usize| {
+            if *v > range.start && *v >= range.end {
+                *v = *v - range.len() + len;
+            }
+        };
+
+        start(&mut self.span.start);
+        end(&mut self.span.end);
+        match &mut self.kind {
+            Kind::Object(members) => members.iter_mut().for_each(|v| {
+                start(&mut v.key.start);
+                end(&mut v.key.end);
+                v.val.shift(range, len);
+            }),
+            Kind::Array(vals) => vals.iter_mut().for_each(|v| v.shift(range, len)),
+            _ => {}
+        }
+    }
+}
+
+struct Splice {
+    range: Range<usize>,
+    text: String,
+    // offset of the edited item within the text.
+    item: Option<usize>,
+}
+
+impl Splice {
+    fn cut(range: Range<usize>) -> Self {
+        Self {
+            range,
+            text: String::new(),
+            item: None,
+        }
+    }
+
+    fn put(pos: usize, prefix: &str, text: &str, suffix: &str) -> Self {
+        Self {
+            range: pos..pos,
+            text: format!("{prefix}{text}{suffix}"),
+            item: Some(prefix.len()),
+        }
+    }
+}
+
+enum Change {
+    Remove,
+    Replace,
+    // key along with its quoted length for members, and the offset of the value.
+    Add {
+        key: Option<(String, usize)>,
+        val: usize,
+    },
+}
+
+struct Edit {
+    splices: Vec<Splice>,
+    idx: usize,
+    change: Change,
+}
+
+// a member or an element, keys span the same as values for the latter.
+struct Item {
+    key: Range<usize>,
+    val: Range<usize>,
+}
+
+struct Layout<'s> {
+    src: &'s str,
+    open: usize,
+    close: usize,
+    keys: Vec<&'s str>,
+    items: Vec<Item>,
+    trivia: Trivia,
+    eol: &'static str,
+}
+
+impl<'s> Layout<'s> {
+    fn new(src: &'s str, node: &'s Node, trivia: Trivia) -> Self {
+        let mut keys = Vec::new();
+        let items = match &node.kind {
+            Kind::Object(members) => members
+                .iter()
+                .map(|v| {
+                    keys.push(&*v.name);
+                    Item {
+                        key: v.key.clone(),
+                        val: v.val.span.clone(),
+                    }
+                })
+                .collect(),
+            Kind::Array(vals) => vals
+                .iter()
+                .map(|v| Item {
+                    key: v.span.clone(),
+                    val: v.span.clone(),
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        // follows the first line break of the document.
+        let eol = match src.find('\n') {
+            Some(v) if src[..v].ends_with('\r') => "\r\n",
+            _ => "\n",
+        };
+
+        Self {
+            src,
+            open: node.span.start,
+            close: node.span.end - 1,
+            keys,
+            items,
+            trivia,
+            eol,
+        }
+    }
+
+    fn plan(&self, seg: &impl JsonPointer, op: Op) -> Result<Edit, EditError> {
+        let len = self.items.len();
+        let object = match self.src.as_bytes()[self.open] {
+            b'{' => true,
+            b'[' => false,
+            _ => return Err(EditError::NotFound),
+        };
+
+        let (idx, key) = match object {
+            true => {
+                let key = seg.as_key().ok_or(EditError::NotFound)?;
+                let idx = self.keys.iter().position(|&v| v == key).unwrap_or(len);
+                (idx, Some(key))
+            }
+            _ => (seg.as_index().ok_or(EditError::NotFound)?, None),
+        };
+
+        let replace = |text: &str| Edit {
+            splices: vec![Splice {
+                range: self.items[idx].val.clone(),
+                text: text.into(),
+                item: Some(0),
+            }],
+            idx,
+            change: Change::Replace,
+        };
+
+        Ok(match op {
+            Op::Remove if idx < len => Edit {
+                splices: self.remove(idx),
+                idx,
+                change: Change::Remove,
+            },
+            Op::Set(text) | Op::Insert(text) if object && idx < len => replace(text),
+            Op::Set(text) if idx < len => replace(text),
+            Op::Set(text) | Op::Insert(text) if idx <= len => match key {
+                Some(key) => {
+                    let (quoted, colon) = (quote(key), self.colon());
+                    Edit {
+                        splices: self.insert(idx, &format!("{quoted}{colon}{text}")),
+                        idx,
+                        change: Change::Add {
+                            key: Some((key.into(), quoted.len())),
+                            val: quoted.len() + colon.len(),
+                        },
+                    }
+                }
+                _ => Edit {
+                    splices: self.insert(idx, text),
+                    idx,
+                    change: Change::Add { key: None, val: 0 },
+                },
+            },
+            _ => return Err(EditError::NotFound),
+        })
+    }
+
+    fn remove(&self, k: usize) -> Vec<Splice> {
+        let len = self.items.len();
+        let item = &self.items[k];
+
+        match self.line_start(k) {
+            Some(start) => {
+                let mut tmp = vec![Splice::cut(start..self.line_end(k))];
+                if k + 1 == len
+                    && k != 0
+                    && self.comma(k).is_none()
+                    && let Some(c) = self.comma(k - 1)
+                {
+                    tmp.push(Splice::cut(c..c + 1));
+                }
+
+                tmp
+            }
+            None if len == 1 => {
+                let end = self.comma(k).map_or(item.val.end, |c| c + 1);
+                vec![Splice::cut(item.key.start..end)]
+            }
+            None if k + 1 != len => {
+                vec![Splice::cut(item.key.start..self.items[k + 1].key.start)]
+            }
+            None => vec![Splice::cut(self.items[k - 1].val.end..item.val.end)],
+        }
+    }
+
+    fn insert(&self, k: usize, text: &str) -> Vec<Splice> {
+        let len = self.items.len();
+        let eol = self.eol;
+
+        if len == 0 {
+            let pos = self.open + 1;
+            return vec![match self.src[pos..self.close].contains('\n') {
+                true => Splice::put(pos, &format!("{eol}{}  ", self.indent(self.open)), text, ""),
+                _ => Splice::put(pos, "", text, ""),
+            }];
+        }
+
+        if k < len {
+            let item = &self.items[k];
+            return vec![match self.line_start(k) {
+                Some(start) => {
+                    Splice::put(start, self.indent(item.key.start), text, &format!(",{eol}"))
+                }
+                _ => Splice::put(item.key.start, "", text, ", "),
+            }];
+        }
+
+        let last = &self.items[len - 1];
+        let comma = self.comma(len - 1);
+
+        match self.line_start(len - 1) {
+            Some(_) => {
+                let bytes = self.src.as_bytes();
+                let mut pos = self
+                    .trivia
+                    .skip_inline(bytes, comma.map_or(last.val.end, |c| c + 1));
+
+                // keeps the line break of the last item in one piece.
+                if bytes.get(pos) == Some(&b'\n') && bytes[pos - 1] == b'\r' {
+                    pos -= 1;
+                }
+
+                let prefix = format!("{eol}{}", self.indent(last.key.start));
+                let mut tmp = vec![match comma {
+                    Some(_) => Splice::put(pos, &prefix, text, ","),
+                    _ => Splice::put(pos, &prefix, text, ""),
+                }];
+
+                if comma.is_none() {
+                    tmp.push(Splice {
+                        range: last.val.end..last.val.end,
+                        text: ",".into(),
+                        item: None,
+                    });
+                }
+
+                tmp
+            }
+            _ => vec![match comma {
+                Some(c) => Splice::put(c + 1, " ", text, ","),
+                _ => Splice::put(last.val.end, ", ", text, ""),
+            }],
+        }
+    }
+
+    // the comma following the item.
+    fn comma(&self, k: usize) -> Option<usize> {
+        let pos = self.trivia.skip(self.src.as_bytes(), self.items[k].val.end);
+        (self.src.as_bytes().get(pos) == Some(&b',')).then_some(pos)
+    }
+
+    // start of the line the item begins on along with its leading comments, if it is not on
+    // the same line as the previous item or the opening bracket.
+    fn line_start(&self, k: usize) -> Option<usize> {
+        let delim = match k {
+            0 => self.open,
+            _ => self.comma(k - 1).unwrap_or(self.items[k - 1].val.end - 1),
+        };
+
+        let pos = self.trivia.skip_inline(self.src.as_bytes(), delim + 1);
+        (self.src.as_bytes().get(pos) == Some(&b'\n')).then_some(pos + 1)
+    }
+
+    // end of the item along with its comma and trailing comment, including the line break.
+    fn line_end(&self, k: usize) -> usize {
+        let bytes = self.src.as_bytes();
+        let pos = self.trivia.skip_inline(
+            bytes,
+            self.comma(k).map_or(self.items[k].val.end, |c| c + 1),
+        );
+
+        match bytes.get(pos) {
+            Some(b'\n') => pos + 1,
+            _ => pos,
+        }
+    }
+
+    // leading whitespace of the line the given offset is on.
+    fn indent(&self, pos: usize) -> &'s str {
+        let bytes = self.src.as_bytes();
+        let start = bytes[..pos]
+            .iter()
+            .rposition(|&v| v == b'\n')
+            .map_or(0, |v| v + 1);
+        let len = bytes[start..]
+            .iter()
+            .take_while(|v| matches!(v, b' ' | b'\t'))
+            .count();
+
+        &self.src[start..start + len]
+    }
+
+    // separator between keys and values, following the last member.
+    fn colon(&self) -> &'s str {
+        self.items
+            .last()
+            .map(|v| &self.src[v.key.end..v.val.start])
+            .filter(|v| v.bytes().all(|c| matches!(c, b':' | b' ' | b'\t')))
+            .unwrap_or(": ")
+    }
+}
+
+fn parse<C: Config>(src: &str, cfg: C) -> Result<Value<'_>, EditError> {
+    let trivia = Trivia::new(&cfg);
+    let val: Value = Parser::new_with(src, cfg)
+        .parse()
+        .map_err(EditError::Invalid)?;
+    let end = trivia.skip(src.as_bytes(), val.end() + 1);
+
+    if end != src.len() {
+        let mut err = Span::<Error>::unexpected_token();
+        err.apply_span(end, end);
+        return Err(EditError::Invalid(err));
+    }
+
+    Ok(val)
+}
+
+// validates the given value and strips the surrounding whitespace and comments, the
+// layout is relative to the stripped text.
+fn trim<C: Config>(src: &str, cfg: C) -> Result<(&str, Node), EditError> {
+    let val = parse(src, cfg)?;
+    let mut node = Node::new(&val);
+
+    node.shift(&(0..val.start()), 0);
+    Ok((&src[val.start()..val.end() + 1], node))
+}
+
+fn quote(s: &str) -> String {
+    let mut tmp = String::with_capacity(s.len() + 2);
+
+    tmp.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => tmp.push_str("\\\""),
+            '\\' => tmp.push_str("\\\\"),
+            '\n' => tmp.push_str("\\n"),
+            '\t' => tmp.push_str("\\t"),
+            '\r' => tmp.push_str("\\r"),
+            c if c < ' ' => _ = write!(tmp, "\\u{:04x}", c as u32),
+            _ => tmp.push(c),
+        }
+    }
+
+    tmp.push('"');
+    tmp
+}
+
+// comment syntaxes skipped around the values, the same ones the parser accepts.
+#[derive(Clone, Copy)]
+struct Trivia {
+    slash: bool,
+    hash: bool,
+}
+
+impl Trivia {
+    #[allow(unused_variables)]
+    fn new<C: Config>(cfg: &C) -> Self {
+        #[cfg(feature = "comment")]
+        return Self {
+            slash: cfg.comments().contains(CommentSyntax::SLASH),
+            hash: cfg.comments().contains(CommentSyntax::HASH),
+        };
+
+        #[cfg(not(feature = "comment"))]
+        Self {
+            slash: false,
+            hash: false,
+        }
+    }
+
+    // skips whitespace and comments on the current line, stopping at the line break.
+    fn skip_inline(self, s: &[u8], mut idx: usize) -> usize {
+        loop {
+            match s.get(idx) {
+                Some(b' ' | b'\t' | b'\r') => idx += 1,
+                Some(b'/') if self.slash && s.get(idx + 1) == Some(&b'*') => {
+                    idx = match s[idx + 2..].windows(2).position(|v| v == b"*/") {
+                        Some(v) => idx + 2 + v + 2,
+                        _ => s.len(),
+                    }
+                }
+                Some(b'/') if self.slash && s.get(idx + 1) == Some(&b'/') => {
+                    return line_end(s, idx);
+                }
+                Some(b'#') if self.hash => return line_end(s, idx),
+                _ => return idx,
+            }
+        }
+    }
+
+    // skips whitespace, comments and line breaks.
+    fn skip(self, s: &[u8], mut idx: usize) -> usize {
+        loop {
+            idx = self.skip_inline(s, idx);
+            match s.get(idx) {
+                Some(b'\n') => idx += 1,
+                _ => return idx,
+            }
+        }
+    }
+}
+
+fn line_end(s: &[u8], idx: usize) -> usize {
+    s[idx..]
+        .iter()
+        .position(|&v| v == b'\n')
+        .map_or(s.len(), |v| idx + v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, Node, parse};
+    use crate::{config::RTConfig, jsonp};
+
+    fn check(doc: &Document) {
+        let root = Node::new(&parse(doc.as_str(), doc.cfg.clone()).unwrap());
+        assert_eq!(doc.root, root);
+    }
+
+    // the kept layout must match the one of the edited source.
+    #[test]
+    fn layout_after_edits() {
+        let src = "{\r\n  \"a\": [1, 2],\r\n  \"b\": {\"c\": 1}\r\n}";
+        let mut doc =
+            Document::parse_with(src, RTConfig::new().allow_trailing_comma(true)).unwrap();
+
+        doc.set(jsonp!["a", 1], "[3, {\"d\": 4}]").unwrap();
+        check(&doc);
+        doc.insert(jsonp!["a", 0], "0").unwrap();
+        check(&doc);
+        doc.set(jsonp!["a", 3], "5").unwrap();
+        check(&doc);
+        doc.set(jsonp!["e"], "{\"f\": [6]}").unwrap();
+        check(&doc);
+        doc.set(jsonp!["b", "g"], "null").unwrap();
+        check(&doc);
+        doc.remove(jsonp!["a", 1]).unwrap();
+        check(&doc);
+        doc.remove(jsonp!["b"]).unwrap();
+        check(&doc);
+        doc.set(jsonp!["e", "f", 1], "7").unwrap();
+        check(&doc);
+
+        assert_eq!(doc.get(jsonp!["e", "f", 1]), Some("7"));
+        assert_eq!(
+            doc.as_str(),
+            "{\r\n  \"a\": [0, [3, {\"d\": 4}], 5],\r\n  \"e\": {\"f\": [6, 7]}\r\n}"
+        );
+
+        doc.set(jsonp![], " [1] ").unwrap();
+        check(&doc);
+        assert_eq!(doc.as_str(), "[1]");
+    }
+
+    // without comments enabled, `#` and `//` are not skipped as such.
+    #[test]
+    fn comments_follow_config() {
+        let cfg = RTConfig::new();
+        assert!(Document::parse_with("{} // x", cfg.clone()).is_err());
+        assert!(Document::parse_with("{} # x", cfg).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "comment")]
+    fn layout_with_comments() {
+        let src = "{\n  // a\n  \"a\": 1, # b\n  \"b\": [ /* c */ 2 ] // d\n}\n";
+        let cfg = RTConfig::new().comment_syntax(crate::config::CommentSyntax::ALL);
+        let mut doc = Document::parse_with(src, cfg).unwrap();
+
+        doc.insert(jsonp!["b", 0], "1").unwrap();
+        check(&doc);
+        doc.set(jsonp!["c"], "3").unwrap();
+        check(&doc);
+        doc.remove(jsonp!["a"]).unwrap();
+        check(&doc);
+
+        assert_eq!(
+            doc.as_str(),
+            "{\n  \"b\": [ /* c */ 1, 2 ], // d\n  \"c\": 3\n}\n"
+        );
+    }
+}
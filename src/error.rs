@@ -1,6 +1,32 @@
-/// Represents the type of error that occurred while parsing.
+use core::{
+    fmt::{self, Display, Formatter},
+    mem::discriminant,
+};
+
+/// Represents error occurred while parsing.
+///
+/// Carries the byte offset of where parsing stopped, which is the same as
+/// [`Parser::offset`](crate::Parser::offset) right after the failure. For the exact span of the
+/// erroneous token, parse into `span::Value` with the `span` feature.
+///
+/// # Example
+/// ```
+/// use flexon::{ErrorKind, Value};
+///
+/// let err = flexon::parse::<_, Value>(r#"{"a": [1, 2 3]}"#).unwrap_err();
+///
+/// assert_eq!(err, ErrorKind::UnexpectedToken);
+/// assert_eq!(err.offset(), 12);
+/// ```
 #[derive(Debug, PartialEq, Eq)]
-pub enum Error {
+pub struct Error {
+    kind: ErrorKind,
+    offset: usize,
+}
+
+/// Represents the type of error.
+#[derive(Debug)]
+pub enum ErrorKind {
     /// Unexpected EOF while parsing.
     Eof,
 
@@ -39,74 +65,213 @@ pub enum Error {
 
     /// Number is bigger than it can represent.
     NumberOverflow,
+
+    /// Nesting depth of arrays and objects exceeded the configured limit.
+    DepthLimitExceeded,
+
+    /// String length exceeded the configured limit.
+    StringTooLong,
+
+    /// Number of elements in an array or entries in an object exceeded the configured limit.
+    ContainerTooLarge,
+
+    /// Allocated bytes exceeded the configured limit.
+    AllocLimitExceeded,
+
+    /// Object contains the same key more than once.
+    DuplicateKey,
+
+    /// I/O error while reading the source.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl Error {
+    /// Returns the error kind.
+    #[inline]
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Returns the byte offset of where parsing stopped.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl From<ErrorKind> for Error {
+    #[cold]
+    fn from(kind: ErrorKind) -> Self {
+        Self { kind, offset: 0 }
+    }
+}
+
+impl PartialEq<ErrorKind> for Error {
+    #[inline]
+    fn eq(&self, other: &ErrorKind) -> bool {
+        self.kind == *other
+    }
+}
+
+impl PartialEq for ErrorKind {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            #[cfg(feature = "std")]
+            (Self::Io(a), Self::Io(b)) => a.kind() == b.kind(),
+            _ => discriminant(self) == discriminant(other),
+        }
+    }
+}
+
+impl Eq for ErrorKind {}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            #[cfg(feature = "std")]
+            Self::Io(err) => return Display::fmt(err, f),
+            Self::Eof => "eof while parsing",
+            Self::ExpectedColon => "expected colon",
+            Self::ExpectedValue => "expected value",
+            Self::UnexpectedToken => "unexpected token",
+            Self::UnclosedString => "unclosed string",
+            Self::ControlCharacter => "control character inside string",
+            Self::InvalidEscapeSequnce => "invalid escape sequence",
+            Self::InvalidLiteral => "invalid literal",
+            Self::TrailingComma => "trailing comma",
+            Self::LeadingDecimal => "leading decimal in number",
+            Self::TrailingDecimal => "trailing decimal in number",
+            Self::LeadingZero => "leading zero in number",
+            Self::NumberOverflow => "number too large",
+            Self::DepthLimitExceeded => "nesting depth limit exceeded",
+            Self::StringTooLong => "string too long",
+            Self::ContainerTooLarge => "too many elements",
+            Self::AllocLimitExceeded => "allocation limit exceeded",
+            Self::DuplicateKey => "duplicate key",
+        })
+    }
+}
+
+impl Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.kind, f)
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self.kind {
+            #[cfg(feature = "std")]
+            ErrorKind::Io(err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 impl crate::value::builder::ErrorBuilder for Error {
     #[inline]
     fn eof() -> Self {
-        Self::Eof
+        ErrorKind::Eof.into()
     }
 
     #[inline]
     fn expected_colon() -> Self {
-        Self::ExpectedColon
+        ErrorKind::ExpectedColon.into()
     }
 
     #[inline]
     fn expected_value() -> Self {
-        Self::ExpectedValue
+        ErrorKind::ExpectedValue.into()
     }
 
     #[inline]
     fn trailing_comma() -> Self {
-        Self::TrailingComma
+        ErrorKind::TrailingComma.into()
     }
 
     #[inline]
     fn unclosed_string() -> Self {
-        Self::UnclosedString
+        ErrorKind::UnclosedString.into()
     }
 
     #[inline]
     fn invalid_escape() -> Self {
-        Self::InvalidEscapeSequnce
+        ErrorKind::InvalidEscapeSequnce.into()
     }
 
     #[inline]
     fn control_character() -> Self {
-        Self::ControlCharacter
+        ErrorKind::ControlCharacter.into()
     }
 
     #[inline]
     fn invalid_literal() -> Self {
-        Self::InvalidLiteral
+        ErrorKind::InvalidLiteral.into()
     }
 
     #[inline]
     fn trailing_decimal() -> Self {
-        Self::TrailingDecimal
+        ErrorKind::TrailingDecimal.into()
     }
 
     #[inline]
     fn leading_decimal() -> Self {
-        Self::LeadingDecimal
+        ErrorKind::LeadingDecimal.into()
     }
 
     #[inline]
     fn leading_zero() -> Self {
-        Self::LeadingZero
+        ErrorKind::LeadingZero.into()
     }
 
     #[inline]
     fn number_overflow() -> Self {
-        Self::NumberOverflow
+        ErrorKind::NumberOverflow.into()
     }
 
     #[inline]
     fn unexpected_token() -> Self {
-        Self::UnexpectedToken
+        ErrorKind::UnexpectedToken.into()
+    }
+
+    #[inline]
+    fn depth_limit_exceeded() -> Self {
+        ErrorKind::DepthLimitExceeded.into()
+    }
+
+    #[inline]
+    fn string_too_long() -> Self {
+        ErrorKind::StringTooLong.into()
+    }
+
+    #[inline]
+    fn container_too_large() -> Self {
+        ErrorKind::ContainerTooLarge.into()
+    }
+
+    #[inline]
+    fn alloc_limit_exceeded() -> Self {
+        ErrorKind::AllocLimitExceeded.into()
+    }
+
+    #[inline]
+    fn duplicate_key() -> Self {
+        ErrorKind::DuplicateKey.into()
+    }
+
+    #[inline]
+    #[cfg(feature = "std")]
+    fn io(err: std::io::Error) -> Self {
+        ErrorKind::Io(err).into()
     }
 
     #[inline]
     fn apply_span(&mut self, _: usize, _: usize) {}
+
+    #[inline]
+    fn apply_offset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
 }
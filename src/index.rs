@@ -0,0 +1,504 @@
+//! Structural index of JSON documents for repeated lookups.
+
+use crate::{
+    Error, LazyValue, Parser,
+    pointer::JsonPointer,
+    simd::for_each_structural,
+    source::{NonVolatile, Source},
+    value::{builder::ErrorBuilder, lazy::Raw},
+};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+
+/// Positions of the structural characters of a JSON document, along with the matching
+/// bracket of every array and object.
+///
+/// Building the index takes a single SIMD pass over the document after validating it.
+/// Lookups then jump over the arrays and objects that are not on the path without reading
+/// them, which pays off when querying the same large document many times.
+///
+/// A parser over `&StructuralIndex` jumps through the index in [`Parser::skip_to`], and lazy
+/// values obtained from [`StructuralIndex::pointer`] or [`StructuralIndex::value`] keep it, so
+/// [`LazyValue::pointer`](crate::value::lazy::Value::pointer), lookups and
+/// [`actual_len`](crate::value::lazy::Array::actual_len) no longer scan over the elements.
+///
+/// Only strict JSON is supported, as comments and dialect extensions are not indexed.
+///
+/// # Example
+/// ```
+/// use flexon::{index::StructuralIndex, jsonp};
+///
+/// let src = r#"{"users": [{"name": "Walter", "tags": [1, 2, 3]}, {"name": "Jesse"}]}"#;
+/// let index = StructuralIndex::new(src)?;
+///
+/// assert_eq!(index.len(jsonp!["users", 0, "tags"]), Some(3));
+/// assert!(index.pointer(jsonp!["users", 1, "name"]).unwrap().is_str());
+///
+/// # #[cfg(feature = "serde")] {
+/// use serde::Deserialize;
+///
+/// let mut parser = index.parser_at(jsonp!["users", 1, "name"]).unwrap();
+/// assert_eq!(String::deserialize(&mut parser).unwrap(), "Jesse");
+///
+/// let mut parser = flexon::Parser::new(&index);
+/// parser.skip_to::<flexon::Error, _>(jsonp!["users", 0, "tags", 2]).unwrap();
+/// assert_eq!(u32::deserialize(&mut parser).unwrap(), 3);
+/// # }
+///
+/// let mut users = index.pointer(["users"]).unwrap();
+/// assert_eq!(users.as_array().unwrap().actual_len(), 2);
+/// # Ok::<(), flexon::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct StructuralIndex<'a> {
+    src: &'a str,
+    pos: Vec<usize>,
+    // index of the matching bracket for opening ones, number of elements for closing ones.
+    jump: Vec<usize>,
+}
+
+impl<'a> StructuralIndex<'a> {
+    /// Validates the given source and builds its index.
+    pub fn new(src: &'a str) -> Result<Self, Error> {
+        Parser::new(src).validate()?;
+        Ok(unsafe { Self::new_unchecked(src) })
+    }
+
+    /// Builds the index of the given source without validating it.
+    ///
+    /// # Safety
+    ///
+    /// The source must be valid JSON, otherwise lookups have no guarantee.
+    pub unsafe fn new_unchecked(src: &'a str) -> Self {
+        let bytes = src.as_bytes();
+        let mut pos = Vec::new();
+        let mut jump = Vec::new();
+        // opening bracket along with the number of commas seen so far.
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+
+        for_each_structural(bytes, |v| {
+            let idx = pos.len();
+            pos.push(v);
+            jump.push(0);
+
+            match bytes[v] {
+                b'{' | b'[' => stack.push((idx, 0)),
+                b'}' | b']' => {
+                    let Some((open, commas)) = stack.pop() else {
+                        return;
+                    };
+
+                    jump[open] = idx;
+                    jump[idx] = match commas {
+                        0 if skip_whitespace(bytes, pos[open] + 1) == v => 0,
+                        n => n + 1,
+                    };
+                }
+                b',' => {
+                    if let Some((_, commas)) = stack.last_mut() {
+                        *commas += 1;
+                    }
+                }
+                _ => {}
+            }
+        });
+
+        Self { src, pos, jump }
+    }
+
+    /// Returns the source the index was built from.
+    #[inline]
+    pub fn source(&self) -> &'a str {
+        self.src
+    }
+
+    /// Returns the offset of the value at the given path, `None` if it does not exist.
+    #[inline]
+    pub fn offset<P>(&self, p: P) -> Option<usize>
+    where
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        self.find(p).map(|v| v.0)
+    }
+
+    /// Returns the root value in its raw form, keeping the index for further lookups.
+    #[inline]
+    pub fn value(&self) -> LazyValue<'_> {
+        let off = skip_whitespace(self.src.as_bytes(), 0);
+        LazyValue::Raw(Indexed::new(self, off, 0).raw())
+    }
+
+    /// Looks up a value by the given path.
+    ///
+    /// Same as [`LazyValue::pointer`](crate::value::lazy::Value::pointer), except that
+    /// the value is always returned in its raw form. The index is kept for further lookups.
+    #[inline]
+    pub fn pointer<P>(&self, p: P) -> Option<LazyValue<'_>>
+    where
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        let (off, idx) = self.find(p)?;
+        Some(LazyValue::Raw(Indexed::new(self, off, idx).raw()))
+    }
+
+    /// Returns a parser right before the value at the given path, `None` if it does not exist.
+    ///
+    /// The parser reads the rest of the source, so [`Parser::offset`] stays relative to the
+    /// whole document.
+    #[inline]
+    pub fn parser_at<P>(&self, p: P) -> Option<Parser<'a, &'a str>>
+    where
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        let (off, _) = self.find(p)?;
+        let mut parser = Parser::new(self.src);
+        parser.inc(off);
+        Some(parser)
+    }
+
+    /// Returns the number of elements or members of the array or object at the given path.
+    ///
+    /// Returns `None` if the path does not exist or does not lead to an array or object.
+    #[inline]
+    pub fn len<P>(&self, p: P) -> Option<usize>
+    where
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        let (off, idx) = self.find(p)?;
+        match self.src.as_bytes()[off] {
+            b'{' | b'[' => Some(self.count(idx)),
+            _ => None,
+        }
+    }
+
+    // returns the offset of the value and the index of its first structural character,
+    // or the one right after it in case of other literals.
+    fn find<P>(&self, p: P) -> Option<(usize, usize)>
+    where
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        let off = skip_whitespace(self.src.as_bytes(), 0);
+        self.find_from(off, 0, p)
+    }
+
+    // same as `find`, starting from the value at the given offset and index.
+    pub(crate) fn find_from<P>(
+        &self,
+        mut off: usize,
+        mut idx: usize,
+        p: P,
+    ) -> Option<(usize, usize)>
+    where
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        for pointer in p {
+            (off, idx) = self.step(off, idx, pointer)?;
+        }
+
+        Some((off, idx))
+    }
+
+    // returns the element or member of the array or object at the given offset and index
+    // that the pointer leads to.
+    pub(crate) fn step<T: JsonPointer>(
+        &self,
+        off: usize,
+        idx: usize,
+        pointer: T,
+    ) -> Option<(usize, usize)> {
+        let bytes = self.src.as_bytes();
+        let close = match bytes.get(off) {
+            Some(b'{' | b'[') => self.jump[idx],
+            _ => return None,
+        };
+        let mut i = idx + 1;
+
+        if let Some(key) = pointer.as_key()
+            && bytes[off] == b'{'
+        {
+            while i != close {
+                // the key followed by the colon
+                let matches = unsafe {
+                    let mut tmp = Parser::new(self.src);
+                    tmp.inc(self.pos[i].wrapping_add(1));
+                    tmp.string_match_unchecked(key)
+                };
+                let val = skip_whitespace(bytes, self.pos[i + 1] + 1);
+
+                if matches {
+                    return Some((val, i + 2));
+                }
+
+                i = self.next(val, i + 2);
+                if i == close {
+                    break;
+                }
+
+                // skip ','
+                i += 1;
+            }
+        } else if let Some(mut n) = pointer.as_index()
+            && bytes[off] == b'['
+            && n < self.jump[close]
+        {
+            loop {
+                let val = skip_whitespace(bytes, self.pos[i - 1] + 1);
+                if n == 0 {
+                    return Some((val, i));
+                }
+
+                n -= 1;
+                // skip ','
+                i = self.next(val, i) + 1;
+            }
+        }
+
+        None
+    }
+
+    // follows the path from the value at the given offset and index, returning the offset
+    // reached along with the number of containers entered on the way.
+    pub(crate) fn walk<E, P>(
+        &self,
+        mut off: usize,
+        mut idx: usize,
+        p: P,
+    ) -> (usize, usize, Result<(), E>)
+    where
+        E: ErrorBuilder,
+        P: IntoIterator,
+        P::Item: JsonPointer,
+    {
+        let mut depth = 0;
+
+        for pointer in p {
+            let container = match self.src.as_bytes()[off] {
+                b'{' => pointer.as_key().is_some(),
+                b'[' => pointer.as_index().is_some(),
+                _ => false,
+            };
+
+            (off, idx) = match self.step(off, idx, pointer) {
+                Some(v) => v,
+                _ if container => return (off, depth + 1, Err(E::expected_value())),
+                _ => return (off, depth, Err(E::unexpected_token())),
+            };
+            depth += 1;
+        }
+
+        (off, depth, Ok(()))
+    }
+
+    // index of the value at the given offset, if it starts with a structural character.
+    #[inline]
+    pub(crate) fn locate(&self, off: usize) -> Option<usize> {
+        self.pos.binary_search(&off).ok()
+    }
+
+    // number of elements or members of the array or object at the given index.
+    #[inline]
+    pub(crate) fn count(&self, idx: usize) -> usize {
+        self.jump[self.jump[idx]]
+    }
+
+    // index of the structural character right after the value.
+    #[inline]
+    fn next(&self, off: usize, idx: usize) -> usize {
+        match self.src.as_bytes()[off] {
+            b'{' | b'[' => self.jump[idx] + 1,
+            b'"' => idx + 1,
+            _ => idx,
+        }
+    }
+}
+
+impl Source for &StructuralIndex<'_> {
+    const UTF8: bool = true;
+    const INSITU: bool = false;
+    const NULL_PADDED: bool = false;
+
+    type Volatility = NonVolatile;
+
+    #[inline(always)]
+    fn ptr(&mut self, offset: usize) -> *const u8 {
+        unsafe { self.src.as_ptr().add(offset) }
+    }
+
+    #[inline(always)]
+    fn ptr_mut(&mut self, _: usize) -> *mut u8 {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn trim(&mut self, _: usize) {}
+
+    #[inline(always)]
+    fn len(&mut self) -> usize {
+        self.src.len()
+    }
+
+    #[inline(always)]
+    fn index(&self) -> Option<&StructuralIndex<'_>> {
+        Some(self)
+    }
+}
+
+/// Position of a value in the index, kept by the lazy values obtained from it.
+#[derive(Clone, Copy)]
+pub(crate) struct Indexed<'a> {
+    index: &'a StructuralIndex<'a>,
+    pub(crate) off: usize,
+    idx: usize,
+}
+
+impl Debug for Indexed<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Indexed")
+            .field("off", &self.off)
+            .field("idx", &self.idx)
+            .finish()
+    }
+}
+
+impl<'a> Indexed<'a> {
+    #[inline]
+    fn new(index: &'a StructuralIndex<'a>, off: usize, idx: usize) -> Self {
+        Self { index, off, idx }
+    }
+
+    /// Moves to the element or member the pointer leads to.
+    #[inline]
+    pub(crate) fn step<T: JsonPointer>(self, pointer: T) -> Option<Self> {
+        let (off, idx) = self.index.step(self.off, self.idx, pointer)?;
+        Some(Self::new(self.index, off, idx))
+    }
+
+    /// Number of elements or members, the value must be an array or object.
+    #[inline]
+    pub(crate) fn count(self) -> usize {
+        self.index.count(self.idx)
+    }
+
+    /// Offset of the key of a member, the value must have been reached by a key.
+    #[inline]
+    pub(crate) fn key(self) -> usize {
+        // the key and the colon come right before the value
+        self.index.pos[self.idx - 2]
+    }
+
+    #[inline]
+    pub(crate) fn raw(self) -> Raw<'a> {
+        Raw(
+            unsafe { self.index.src.get_unchecked(self.off..) },
+            Some(self),
+        )
+    }
+}
+
+#[inline]
+fn skip_whitespace(src: &[u8], mut idx: usize) -> usize {
+    while idx < src.len() && matches!(src[idx], b' ' | b'\t' | b'\n' | b'\r') {
+        idx += 1;
+    }
+
+    idx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorKind, jsonp, pointer::Pointer};
+
+    const SRC: &str =
+        r#" {"a": [1, {"b": "x\"y", "c": []}, [[], [2, 3]], "z"], "d": {}, "e": null} "#;
+
+    fn paths() -> Vec<Vec<Pointer<'static>>> {
+        alloc::vec![
+            jsonp![].into(),
+            jsonp!["a"].into(),
+            jsonp!["a", 0].into(),
+            jsonp!["a", 1, "b"].into(),
+            jsonp!["a", 1, "c"].into(),
+            jsonp!["a", 2, 1, 1].into(),
+            jsonp!["a", 3].into(),
+            jsonp!["a", 4].into(),
+            jsonp!["a", 2, 0, 0].into(),
+            jsonp!["a", "b"].into(),
+            jsonp!["d", "x"].into(),
+            jsonp!["e"].into(),
+            jsonp!["e", 0].into(),
+            jsonp!["x\"y"].into(),
+        ]
+    }
+
+    #[test]
+    fn indexed_lookups() {
+        let index = StructuralIndex::new(SRC).unwrap();
+        let root = index.value();
+        let plain = LazyValue::Raw(Raw(SRC.trim_start(), None));
+
+        for p in paths() {
+            let mut a = Parser::new(&index);
+            let mut b = Parser::new(SRC);
+            let res = a.skip_to::<Error, _>(p.iter().map(to_pointer));
+            let other = b.skip_to::<Error, _>(p.iter().map(to_pointer));
+
+            assert_eq!(
+                res.as_ref().map_err(Error::kind),
+                other.as_ref().map_err(Error::kind)
+            );
+            if res.is_ok() {
+                assert_eq!(a.offset(), b.offset());
+            }
+
+            let (a, b) = (
+                root.pointer(p.iter().map(to_pointer)),
+                plain.pointer(p.iter().map(to_pointer)),
+            );
+            assert_eq!(a.is_some(), res.is_ok());
+            assert_eq!(
+                a.map(|v| v.as_raw().map(|v| v.trim_to_value().as_ptr())),
+                b.map(|v| v.as_raw().map(|v| v.trim_to_value().as_ptr())),
+            );
+        }
+    }
+
+    #[test]
+    fn indexed_containers() {
+        let index = StructuralIndex::new(SRC).unwrap();
+        let mut root = index.value();
+
+        let a = root.get("a").unwrap();
+        assert!(a.as_raw().unwrap().1.is_some());
+        assert_eq!(a.as_array().unwrap().actual_len(), 4);
+        assert_eq!(a.get(1).unwrap().get("b").unwrap().as_str(), Some("x\"y"));
+        assert_eq!(a.get(2).unwrap().as_array().unwrap().actual_len(), 2);
+        assert!(a.get(4).is_none());
+
+        let c = index.pointer(jsonp!["a", 1, "c"]);
+        assert_eq!(c.unwrap().as_array().unwrap().actual_len(), 0);
+        assert_eq!(root.get("d").unwrap().as_object().unwrap().actual_len(), 0);
+        assert_eq!(root.as_object().unwrap().actual_len(), 3);
+    }
+
+    #[test]
+    fn indexed_depth_limit() {
+        let index = StructuralIndex::new(SRC).unwrap();
+        let cfg = crate::config::RTConfig::new().max_depth(2);
+        let mut parser = Parser::new_with(&index, cfg);
+
+        let err = parser.skip_to::<Error, _>(jsonp!["a", 2, 1]).unwrap_err();
+        assert_eq!(err, ErrorKind::DepthLimitExceeded);
+    }
+
+    fn to_pointer<'a>(p: &'a Pointer<'_>) -> Pointer<'a> {
+        p.as_key()
+            .map_or_else(|| p.as_index().unwrap().into(), Pointer::Key)
+    }
+}
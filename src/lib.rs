@@ -20,6 +20,10 @@ pub mod value;
 
 #[cfg(feature = "comment")]
 mod comment;
+#[cfg(all(feature = "span", feature = "alloc"))]
+pub mod cst;
+#[cfg(feature = "alloc")]
+pub mod index;
 pub mod pointer;
 #[cfg(feature = "serde")]
 pub mod serde;
@@ -29,7 +33,10 @@ pub mod span;
 use crate::{pointer::JsonPointer, source::Source, value::builder::ValueBuilder};
 
 #[doc(inline)]
-pub use {error::Error, parser::Parser};
+pub use {
+    error::{Error, ErrorKind},
+    parser::{ArrayStream, MinifyOptions, Parser, Stream},
+};
 
 #[doc(inline)]
 #[cfg(feature = "alloc")]
@@ -44,12 +51,15 @@ pub use serde::de::{
 
 #[doc(inline)]
 #[cfg(all(feature = "serde", feature = "alloc"))]
-pub use serde::de::{from_mut_null_padded, from_null_padded, get_from, get_from_unchecked};
+pub use serde::{
+    de::{from_mut_null_padded, from_null_padded, get_from, get_from_unchecked},
+    from_value,
+};
 
 #[doc(inline)]
 #[cfg(all(feature = "serde", feature = "std"))]
 pub use serde::{
-    de::{from_reader, from_reader_unchecked},
+    de::{from_reader, from_reader_iter, from_reader_unchecked},
     ser::{to_string, to_string_pretty, to_vec, to_vec_pretty, to_writer, to_writer_pretty},
 };
 
@@ -74,6 +84,115 @@ pub fn parse<'a, S: Source + 'a, V: ValueBuilder<'a, S>>(s: S) -> Result<V, V::E
     Parser::new(s).parse()
 }
 
+/// Checks whether the given source is a single valid JSON document.
+///
+/// This is a convenience function that creates a parser with default configuration
+/// and immediately validates the input. It is equivalent to `Parser::new(src).validate()`.
+///
+/// # Example
+/// ```
+/// use flexon::{ErrorKind, validate};
+///
+/// assert_eq!(validate(r#"{"width": 20, "height": 50}"#), Ok(()));
+/// assert_eq!(validate(r#"{"width": 20,}"#).unwrap_err(), ErrorKind::TrailingComma);
+/// ```
+#[inline]
+pub fn validate<'a, S: Source + 'a>(s: S) -> Result<(), Error> {
+    Parser::new(s).validate()
+}
+
+/// Minifies the given source, dropping insignificant whitespace.
+///
+/// This is a convenience function that creates a parser with default configuration
+/// and immediately minifies the input. It is equivalent to `Parser::new(src).minify()`.
+///
+/// # Example
+/// ```
+/// use flexon::minify;
+///
+/// let json = "{\n  \"width\": 20,\n  \"height\": 50\n}";
+///
+/// assert_eq!(minify(json)?, r#"{"width":20,"height":50}"#);
+/// # Ok::<(), flexon::Error>(())
+/// ```
+#[inline]
+#[cfg(feature = "alloc")]
+pub fn minify<'a, S: Source + 'a>(s: S) -> Result<alloc::string::String, Error> {
+    Parser::new(s).minify()
+}
+
+/// Minifies the given buffer in place, returning the length of the output.
+///
+/// This is a convenience function that creates a parser with default configuration
+/// and immediately minifies the input. It is equivalent to `Parser::from_mut_slice(src).minify_in_place()`.
+///
+/// # Example
+/// ```
+/// use flexon::minify_in_place;
+///
+/// let mut buf = *b"[1, 2, 3]";
+/// let len = minify_in_place(&mut buf)?;
+///
+/// assert_eq!(&buf[..len], b"[1,2,3]");
+/// # Ok::<(), flexon::Error>(())
+/// ```
+#[inline]
+pub fn minify_in_place(s: &mut [u8]) -> Result<usize, Error> {
+    Parser::from_mut_slice(s).minify_in_place()
+}
+
+/// Reformats the given source token by token using the given format.
+///
+/// This is a convenience function that creates a parser with default configuration
+/// and immediately reformats the input. It is equivalent to `Parser::new(src).reformat(f)`.
+///
+/// # Example
+/// ```
+/// use flexon::{reformat, serde::format::Pretty};
+///
+/// let json = r#"{"width": 20.0, "height": 5e1}"#;
+///
+/// assert_eq!(
+///     reformat(json, Pretty::with_indent(" "))?,
+///     "{\n \"width\": 20.0,\n \"height\": 5e1\n}",
+/// );
+/// # Ok::<(), flexon::Error>(())
+/// ```
+#[inline]
+#[cfg(all(feature = "serde", feature = "std"))]
+pub fn reformat<'a, S: Source + 'a, F: serde::format::Format>(
+    s: S,
+    f: F,
+) -> Result<std::string::String, Error> {
+    Parser::new(s).reformat(f)
+}
+
+/// Reformats the given source token by token into the given writer.
+///
+/// This is a convenience function that creates a parser with default configuration
+/// and immediately reformats the input. It is equivalent to `Parser::new(src).reformat_to_writer(w, f)`.
+///
+/// # Example
+/// ```
+/// use flexon::{reformat_to_writer, serde::format::Compact, source::Reader};
+///
+/// let mut out = Vec::new();
+/// reformat_to_writer(Reader::new(&b"[ 1, 2 ]"[..]), &mut out, Compact)?;
+///
+/// assert_eq!(out, b"[1,2]");
+/// # Ok::<(), flexon::Error>(())
+/// ```
+#[inline]
+#[cfg(all(feature = "serde", feature = "std"))]
+pub fn reformat_to_writer<'a, S, W, F>(s: S, w: W, f: F) -> Result<(), Error>
+where
+    S: Source + 'a,
+    W: std::io::Write,
+    F: serde::format::Format,
+{
+    Parser::new(s).reformat_to_writer(w, f)
+}
+
 /// Skips to the given path and parses JSON into the specified type.
 ///
 /// This is a convenience function that creates a parser with default configuration
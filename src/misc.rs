@@ -57,6 +57,20 @@ pub const NON_LIT_LUT: [bool; 256] = {
     tmp
 };
 
+// characters allowed in unquoted object keys, digits can not start one though.
+pub const IDENT_LUT: [bool; 256] = {
+    let mut tmp = [false; 256];
+    let mut idx = 0;
+
+    while idx != 256 {
+        tmp[idx] =
+            matches!(idx as u8, b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'$' | 128..);
+        idx += 1;
+    }
+
+    tmp
+};
+
 pub trait Sealed {}
 
 #[inline(always)]
@@ -0,0 +1,421 @@
+use crate::{
+    Parser,
+    config::{Config, Dialect},
+    misc::*,
+    parser::unicode::*,
+    source::Source,
+    value::builder::{ErrorBuilder, StringBuilder},
+};
+use core::{slice::from_raw_parts, str::from_utf8_unchecked};
+
+// extensions handled by `Parser::number`.
+const NUMBERS: Dialect = Dialect::HEX_NUMBERS
+    .union(Dialect::LEADING_DECIMAL_POINT)
+    .union(Dialect::TRAILING_DECIMAL_POINT)
+    .union(Dialect::PLUS_SIGN)
+    .union(Dialect::NON_FINITE);
+
+// extensions handled by `Parser::dialect_escape`.
+const ESCAPES: Dialect = Dialect::SINGLE_QUOTES
+    .union(Dialect::ESCAPES)
+    .union(Dialect::MULTILINE_STRINGS);
+
+pub(crate) enum Number {
+    Int(u64, bool),
+    Float(f64),
+}
+
+impl<'a, S: Source, C: Config> Parser<'a, S, C> {
+    /// Whether the given byte opens a string.
+    #[inline(always)]
+    pub(crate) fn is_quote(&self, c: u8) -> bool {
+        c == b'"' || c == b'\'' && self.cfg.dialect().contains(Dialect::SINGLE_QUOTES)
+    }
+
+    /// Whether the given byte starts an unquoted object key.
+    #[inline(always)]
+    pub(crate) fn is_ident(&self, c: u8) -> bool {
+        self.cfg.dialect().contains(Dialect::UNQUOTED_KEYS)
+            && IDENT_LUT[c as usize]
+            && !c.is_ascii_digit()
+    }
+
+    /// Whether the given byte starts an object key.
+    #[inline(always)]
+    pub(crate) fn is_key(&self, c: u8) -> bool {
+        self.is_quote(c) || self.is_ident(c)
+    }
+
+    /// Whether the literal starting with the given byte has to go through [`Parser::number`].
+    #[inline(always)]
+    pub(crate) fn is_number(&self, c: u8) -> bool {
+        self.cfg.dialect().intersects(NUMBERS)
+            && matches!(c, b'0'..=b'9' | b'-' | b'+' | b'.' | b'I' | b'N')
+    }
+
+    /// Parses an object key, the parser must be at its first byte.
+    #[inline]
+    pub(crate) unsafe fn key<T, V, E>(&mut self) -> Result<T, E>
+    where
+        V: StringBuilder<'a, S, E> + Into<T>,
+        E: ErrorBuilder,
+    {
+        let tmp = self.cur();
+        match self.is_quote(tmp) {
+            true => self.string::<T, V, E>(),
+            _ => self.ident::<T, V, E>(),
+        }
+    }
+
+    /// Skips an object key, the parser must be at its first byte.
+    #[inline]
+    pub(crate) fn skip_key<E: ErrorBuilder>(&mut self) -> Result<(), E> {
+        let tmp = self.cur();
+        if self.is_quote(tmp) {
+            return self.skip_string();
+        }
+
+        let start = self.idx();
+        self.skip_ident()?;
+
+        match unlikely(self.idx() + 1 - start > self.cfg.max_string_len()) {
+            true => Err(self.string_error(E::string_too_long(), start)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Parses an unquoted object key, the parser must be at its first byte.
+    pub(crate) unsafe fn ident<T, V, E>(&mut self) -> Result<T, E>
+    where
+        V: StringBuilder<'a, S, E> + Into<T>,
+        E: ErrorBuilder,
+    {
+        let start = self.idx();
+        self.skip_ident()?;
+
+        let len = self.idx() + 1 - start;
+        if unlikely(len > self.cfg.max_string_len()) {
+            return Err(self.string_error(E::string_too_long(), start));
+        }
+
+        if self.allocate(len) {
+            return Err(self.string_error(E::alloc_limit_exceeded(), start));
+        }
+
+        let raw = from_raw_parts(self.src.ptr(start), len);
+        let mut buf = V::new();
+
+        buf.on_final_chunk(raw);
+        buf.on_complete(raw)?;
+        #[cfg(feature = "span")]
+        buf.apply_span(start, self.idx());
+
+        Ok(buf.into())
+    }
+
+    /// Skips an unquoted object key, leaving the parser at its last byte.
+    pub(crate) fn skip_ident<E: ErrorBuilder>(&mut self) -> Result<(), E> {
+        let start = self.idx();
+
+        if self.cur() >= 0x80 && !unsafe { self.unicode_char(is_id_start) } {
+            #[allow(unused_mut)]
+            let mut err = E::unexpected_token();
+            #[cfg(feature = "span")]
+            err.apply_span(start, start);
+            return Err(err);
+        }
+
+        while S::NULL_PADDED || self.idx() + 1 < self.src.len() {
+            let next = unsafe { *self.cur_ptr().add(1) };
+            if !IDENT_LUT[next as usize] {
+                break;
+            }
+
+            self.inc(1);
+            if next >= 0x80 && !unsafe { self.unicode_char(is_id_continue) } {
+                self.dec();
+                break;
+            }
+        }
+
+        if !S::UTF8
+            && simdutf8::basic::from_utf8(unsafe {
+                from_raw_parts(self.src.ptr(start), self.idx() + 1 - start)
+            })
+            .is_err()
+        {
+            #[allow(unused_mut)]
+            let mut err = E::unexpected_token();
+            #[cfg(feature = "span")]
+            err.apply_span(start, self.idx());
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    // moves to the last byte of the non ASCII character at the cursor if it satisfies the given
+    // predicate, otherwise stays at its first byte.
+    #[cold]
+    unsafe fn unicode_char(&mut self, f: fn(char) -> bool) -> bool {
+        match self.utf8_char() {
+            Some((c, len)) if f(c) => {
+                self.inc(len - 1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Skips the whitespace allowed by the dialect, the parser must be at its first byte.
+    ///
+    /// Moves to its last byte and returns `true` if there is one, otherwise stays as is.
+    #[inline(always)]
+    pub(crate) fn dialect_space(&mut self, c: u8) -> bool {
+        self.cfg.dialect().contains(Dialect::WHITESPACE)
+            && match c {
+                0x0B | 0x0C => true,
+                0x80.. => unsafe { self.unicode_char(is_space) },
+                _ => false,
+            }
+    }
+
+    // decodes the character at the cursor along with its length, `None` if it is not valid UTF-8.
+    unsafe fn utf8_char(&mut self) -> Option<(char, usize)> {
+        let len = match self.cur() {
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return None,
+        };
+
+        if !S::NULL_PADDED && self.idx() + len > self.src.len() {
+            return None;
+        }
+
+        let s = from_raw_parts(self.cur_ptr(), len);
+        str::from_utf8(s).ok()?.chars().next().map(|c| (c, len))
+    }
+
+    /// Decodes the escape sequences allowed by the dialect, the parser must be at the byte after `'\'`.
+    ///
+    /// Line continuations decode to nothing. Returns `None` if the escape sequence is invalid.
+    #[cold]
+    #[inline(never)]
+    pub(crate) unsafe fn dialect_escape<'esc>(
+        &mut self,
+        buf: &'esc mut [u8; 4],
+    ) -> Option<&'esc [u8]> {
+        let dialect = self.cfg.dialect();
+        if !dialect.intersects(ESCAPES) {
+            return None;
+        }
+
+        let escapes = dialect.contains(Dialect::ESCAPES);
+        let multiline = dialect.contains(Dialect::MULTILINE_STRINGS);
+        let next = match S::NULL_PADDED || self.idx() + 1 < self.src.len() {
+            true => *self.cur_ptr().add(1),
+            _ => 0,
+        };
+
+        buf[0] = match self.cur() {
+            b'\'' if dialect.contains(Dialect::SINGLE_QUOTES) => b'\'',
+            b'v' if escapes => 0x0B,
+            b'0' if escapes && !next.is_ascii_digit() => 0,
+            b'x' if escapes => {
+                if !S::NULL_PADDED && self.idx() + 2 >= self.src.len() {
+                    return None;
+                }
+
+                let tmp = from_utf8_unchecked(from_raw_parts(self.cur_ptr().add(1), 2));
+                let Ok(val) = u8::from_str_radix(tmp, 16) else {
+                    return None;
+                };
+
+                self.inc(2);
+                return Some(char::from(val).encode_utf8(buf).as_bytes());
+            }
+            b'\n' if multiline => return Some(&[]),
+            b'\r' if multiline => {
+                if next == b'\n' {
+                    self.inc(1);
+                }
+
+                return Some(&[]);
+            }
+            // U+2028 and U+2029
+            0xE2 if multiline
+                && next == 0x80
+                && (S::NULL_PADDED || self.idx() + 2 < self.src.len())
+                && matches!(*self.cur_ptr().add(2), 0xA8 | 0xA9) =>
+            {
+                self.inc(2);
+                return Some(&[]);
+            }
+            _ => return None,
+        };
+
+        Some(&buf[..1])
+    }
+
+    /// Parses a number with the extensions allowed by the dialect, the parser must be at its first byte.
+    ///
+    /// Leaves the parser at the last byte of the number.
+    pub(crate) unsafe fn number<E: ErrorBuilder>(&mut self) -> Result<Number, E> {
+        let dialect = self.cfg.dialect();
+        let sign = self.cur();
+        let neg = sign == b'-';
+
+        if neg || sign == b'+' {
+            if !neg && !dialect.contains(Dialect::PLUS_SIGN) {
+                return Err(E::invalid_literal());
+            }
+
+            self.inc(1);
+            if !S::NULL_PADDED && self.idx() == self.src.len() {
+                self.dec();
+                return Err(E::invalid_literal());
+            }
+        }
+
+        let tmp = self.cur();
+        let non_finite = dialect.contains(Dialect::NON_FINITE);
+
+        if tmp == b'I' && non_finite {
+            return self.non_finite(
+                b"Infinity",
+                if neg {
+                    f64::NEG_INFINITY
+                } else {
+                    f64::INFINITY
+                },
+            );
+        }
+
+        if tmp == b'N' && non_finite {
+            return self.non_finite(b"NaN", f64::NAN);
+        }
+
+        if tmp == b'.' {
+            return match dialect.contains(Dialect::LEADING_DECIMAL_POINT) {
+                true => self.loose_float(neg),
+                _ => Err(E::leading_decimal()),
+            };
+        }
+
+        if !tmp.is_ascii_digit() {
+            return Err(E::invalid_literal());
+        }
+
+        if tmp == b'0' && (S::NULL_PADDED || self.idx() + 1 != self.src.len()) {
+            match *self.cur_ptr().add(1) {
+                b'x' | b'X' if dialect.contains(Dialect::HEX_NUMBERS) => return self.hex(neg),
+                b'0'..=b'9' => return Err(E::leading_zero()),
+                _ => {}
+            }
+        }
+
+        let start = self.idx();
+        let (val, is_int) = self.parse_u64();
+
+        if is_int {
+            self.dec();
+            if !neg {
+                return Ok(Number::Int(val, false));
+            }
+
+            if val <= 9223372036854775808 {
+                return Ok(Number::Int(val.wrapping_neg(), true));
+            }
+        }
+
+        if let Some(val) = self.parse_f64(val, neg, start) {
+            return match val.is_finite() {
+                true => Ok(Number::Float(val)),
+                _ => Err(E::number_overflow()),
+            };
+        }
+
+        if *self.cur_ptr().sub(1) != b'.' {
+            return Err(E::invalid_literal());
+        }
+
+        match dialect.contains(Dialect::TRAILING_DECIMAL_POINT) {
+            true => {
+                let tmp = self.idx() - start;
+                self.rewind(tmp);
+                self.loose_float(neg)
+            }
+            _ => Err(E::trailing_decimal()),
+        }
+    }
+
+    unsafe fn non_finite<E: ErrorBuilder>(&mut self, word: &[u8], val: f64) -> Result<Number, E> {
+        let len = word.len();
+
+        if (S::NULL_PADDED || self.idx() + len <= self.src.len())
+            && from_raw_parts(self.cur_ptr(), len) == word
+        {
+            self.inc(len - 1);
+            return Ok(Number::Float(val));
+        }
+
+        Err(E::invalid_literal())
+    }
+
+    unsafe fn hex<E: ErrorBuilder>(&mut self, neg: bool) -> Result<Number, E> {
+        self.inc(2);
+        let start = self.idx();
+        let mut val = 0u64;
+
+        while S::NULL_PADDED || self.idx() != self.src.len() {
+            let tmp = match self.cur() {
+                v @ b'0'..=b'9' => v - b'0',
+                v @ b'a'..=b'f' => v - b'a' + 10,
+                v @ b'A'..=b'F' => v - b'A' + 10,
+                _ => break,
+            };
+
+            if val >> 60 != 0 {
+                return Err(E::number_overflow());
+            }
+
+            val = val << 4 | tmp as u64;
+            self.inc(1);
+        }
+
+        if start == self.idx() {
+            return Err(E::invalid_literal());
+        }
+
+        self.dec();
+        Ok(match neg {
+            false => Number::Int(val, false),
+            _ if val <= 9223372036854775808 => Number::Int(val.wrapping_neg(), true),
+            _ => Number::Float(-(val as f64)),
+        })
+    }
+
+    // numbers with a leading or trailing decimal point, which the fast path rejects.
+    // the parser must be at the first byte after the sign.
+    #[cold]
+    #[inline(never)]
+    unsafe fn loose_float<E: ErrorBuilder>(&mut self, neg: bool) -> Result<Number, E> {
+        let start = self.idx();
+
+        while (S::NULL_PADDED || self.idx() != self.src.len())
+            && matches!(self.cur(), b'0'..=b'9' | b'.' | b'e' | b'E' | b'+' | b'-')
+        {
+            self.inc(1);
+        }
+
+        self.dec();
+        let raw = from_raw_parts(self.src.ptr(start), self.idx() + 1 - start);
+
+        match from_utf8_unchecked(raw).parse::<f64>() {
+            Ok(v) if v.is_finite() => Ok(Number::Float(if neg { -v } else { v })),
+            Ok(_) => Err(E::number_overflow()),
+            _ => Err(E::invalid_literal()),
+        }
+    }
+}
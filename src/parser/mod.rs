@@ -1,10 +1,21 @@
+mod dialect;
+mod rewrite;
 mod skip;
 mod skip_to;
+mod stream;
 mod unchecked;
+mod unicode;
+
+pub use {
+    rewrite::MinifyOptions,
+    stream::{ArrayStream, Stream},
+};
+
+pub(crate) use dialect::Number;
 
 use crate::{
-    JsonPointer,
-    config::{CTConfig, Config},
+    Error, JsonPointer,
+    config::{CTConfig, Config, DuplicateKeys, RTConfig},
     misc::*,
     simd::simd_u64,
     source::*,
@@ -22,7 +33,10 @@ use simdutf8::compat::from_utf8;
 use std::io::Read;
 
 #[cfg(feature = "comment")]
-use {crate::Comment, alloc::vec::Vec};
+use {
+    crate::{Comment, config::CommentSyntax},
+    alloc::vec::Vec,
+};
 
 // todo: trim source when skipping values
 
@@ -31,6 +45,8 @@ pub struct Parser<'a, S: Source + 'a, C: Config = CTConfig> {
     pub(crate) src: S,
     pub(crate) cfg: C,
     cur: Cur,
+    depth: usize,
+    pub(crate) allocated: usize,
     #[cfg(feature = "prealloc")]
     prealloc: usize,
     #[cfg(feature = "comment")]
@@ -39,12 +55,23 @@ pub struct Parser<'a, S: Source + 'a, C: Config = CTConfig> {
 }
 
 // represents the current byte offset.
+#[derive(Clone, Copy)]
 union Cur {
     idx: usize,
     // "pinned" pointer from non volatile source.
     ptr: *mut u8,
 }
 
+// state of the parser to go back to after looking ahead.
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub(crate) struct Checkpoint {
+    cur: Cur,
+    depth: usize,
+    allocated: usize,
+    #[cfg(feature = "comment")]
+    comments: usize,
+}
+
 impl<'a, S: Source, C: Config> Parser<'a, S, C> {
     /// Create a parser with the given source and configuration.
     ///
@@ -86,6 +113,8 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
                 },
                 _ => Cur { idx: usize::MAX },
             },
+            depth: 0,
+            allocated: 0,
             __: PhantomData,
             src,
             cfg,
@@ -108,6 +137,8 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         Parser {
             cfg,
             cur: self.cur,
+            depth: self.depth,
+            allocated: self.allocated,
             src: self.src,
             __: PhantomData,
             #[cfg(feature = "comment")]
@@ -117,11 +148,61 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         }
     }
 
+    /// Creates a lazy value from the `len` bytes at `start`, the value must be skipped already.
+    ///
+    /// Lazy values navigate with the unchecked skipping routines, which only know plain JSON.
+    /// So when a dialect or comments are enabled, the value is checked again without them.
+    #[inline(always)]
+    pub(super) unsafe fn lazy<V: ValueBuilder<'a, S>>(
+        &mut self,
+        start: usize,
+        len: usize,
+    ) -> Result<V, V::Error> {
+        #[cfg(feature = "comment")]
+        let comments = !self.cfg.comments().is_empty();
+        #[cfg(not(feature = "comment"))]
+        let comments = false;
+
+        if comments || !self.cfg.dialect().is_empty() {
+            self.plain(start, len)?;
+        }
+
+        Ok(V::raw(from_raw_parts(self.src.ptr(start), len)))
+    }
+
+    #[inline(never)]
+    #[allow(unused_mut)]
+    unsafe fn plain<E: ErrorBuilder>(&mut self, start: usize, len: usize) -> Result<(), E> {
+        let cfg = RTConfig::new()
+            .require_comma(!self.cfg.comma())
+            .allow_trailing_comma(self.cfg.trailing_comma());
+
+        let raw = from_utf8_unchecked(from_raw_parts(self.src.ptr(start), len));
+        let mut tmp = Parser::new_with(raw, cfg);
+        if tmp.skip_value::<E>().is_ok() {
+            return Ok(());
+        }
+
+        // point at where the plain JSON ends
+        let idx = start + tmp.idx().min(len);
+        let cur = self.idx();
+        self.rewind(cur.wrapping_sub(idx));
+
+        let mut err = E::unexpected_token();
+        #[cfg(feature = "span")]
+        err.apply_span(idx, idx);
+        Err(err)
+    }
+
     /// Parses JSON into the specified type.
     ///
     /// Unlike serde's deserialization API, this method is specifically for parsing
     /// arbitrary JSON values that implement the [`ValueBuilder`] trait.
     ///
+    /// Lazy values can only navigate plain JSON. So when the configuration enables a dialect
+    /// or comments, a value making use of them results in
+    /// [`UnexpectedToken`](crate::ErrorKind::UnexpectedToken) error for lazy value builders.
+    ///
     /// # Example
     /// ```
     /// use flexon::{Parser, Value};
@@ -140,10 +221,47 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         if V::LAZY {
             // "mom, can we have json skipper??"
             // "no we have json skipper at home"
-            self.skip_value()?;
-            Ok(unsafe { V::raw(from_raw_parts(self.src.ptr(0), self.src.len())) })
+            if let Err(e) = self.skip_value() {
+                return Err(self.fail(e));
+            }
+
+            let len = self.src.len();
+            unsafe { self.lazy(0, len) }.map_err(|e| self.fail(e))
         } else {
-            self.value()
+            match self.value() {
+                // a top-level scalar ends wherever the source stops, which may be due to an error
+                Ok(v) => self.take_io_error().map_or(Ok(v), Err),
+                Err(e) => Err(self.fail(e)),
+            }
+        }
+    }
+
+    /// Checks whether the source is a single valid JSON document, without building any value.
+    ///
+    /// Unlike [`Parser::parse`], anything but whitespace after the value is an error. It goes
+    /// through the same SIMD routines used for skipping values and does not allocate, except
+    /// for recording comments. The depth, string length and container length limits apply as
+    /// they do when parsing, whereas the allocation limit does not as nothing is built.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{ErrorKind, Parser};
+    ///
+    /// assert_eq!(Parser::from_str(r#"{"a": [1, 2]}"#).validate(), Ok(()));
+    ///
+    /// let err = Parser::from_str(r#"{"a": 1} {"b": 2}"#).validate().unwrap_err();
+    ///
+    /// assert_eq!(err, ErrorKind::UnexpectedToken);
+    /// assert_eq!(err.offset(), 9);
+    /// ```
+    pub fn validate(&mut self) -> Result<(), Error> {
+        if let Err(err) = self.skip_value() {
+            return Err(self.fail(err));
+        }
+
+        match self.skip_whitespace() {
+            0 => self.take_io_error().map_or(Ok(()), Err),
+            _ => Err(self.fail(Error::unexpected_token())),
         }
     }
 
@@ -207,26 +325,29 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         }
 
         unsafe {
-            let char = self._skip_to(p)?;
+            let char = match self._skip_to(p) {
+                Ok(v) => v,
+                Err(e) => return Err(self.fail(e)),
+            };
 
             if V::LAZY {
                 // source is non volatile
-                let start = self.cur_ptr();
+                let start = self.idx();
                 match char {
                     b'"' => self.skip_string(),
                     b'{' => self.skip_object(),
                     b'[' => self.skip_array(),
-                    0 => return Err(V::Error::expected_value()),
+                    0 => Err(V::Error::expected_value()),
                     _ => self.skip_literal(),
-                }?;
-
-                Ok(V::raw(from_raw_parts(
-                    start,
-                    self.cur_ptr().offset_from_unsigned(start) + 1,
-                )))
+                }
+                .and_then(|_| {
+                    let len = self.idx() + 1 - start;
+                    self.lazy(start, len)
+                })
+                .map_err(|e| self.fail(e))
             } else {
                 match char {
-                    b'"' => self.string::<_, V::String, _>(),
+                    v if self.is_quote(v) => self.string::<_, V::String, _>(),
                     b'{' => self.object(),
                     b'[' => self.array(),
                     0 => {
@@ -238,7 +359,84 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
                     }
                     _ => self.literal(),
                 }
+                .map_err(|e| self.fail(e))
+            }
+        }
+    }
+
+    /// Returns an iterator over successive top-level values.
+    ///
+    /// Useful for newline delimited JSON (NDJSON / JSON Lines) or any other input
+    /// consisting of whitespace separated values. See [`Stream`] for more details.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{Parser, Value};
+    ///
+    /// let src = "{\"id\": 1}\n{\"id\": 2}\n";
+    /// let mut parser = Parser::from_str(src);
+    ///
+    /// for val in parser.iter::<Value>() {
+    ///     println!("{}", val?["id"].as_u64().unwrap());
+    /// }
+    /// # Ok::<(), flexon::Error>(())
+    /// ```
+    #[inline]
+    pub fn iter<V: ValueBuilder<'a, S>>(&mut self) -> Stream<'_, 'a, S, C, V> {
+        Stream::new(self)
+    }
+
+    /// Returns an iterator over the elements of the upcoming array.
+    ///
+    /// Meant to be used after [`Parser::skip_to`] to stream a large array element by
+    /// element without building the whole container. See [`ArrayStream`] for more details.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{Error, OwnedValue, Parser};
+    ///
+    /// let src = r#"{"meta": {}, "items": [{"id": 1}, {"id": 2}]}"#;
+    /// let mut parser = Parser::from_str(src);
+    ///
+    /// parser.skip_to::<Error, _>(["items"])?;
+    /// for item in parser.iter_array::<OwnedValue>() {
+    ///     println!("{}", item?["id"].as_u64().unwrap());
+    /// }
+    /// # Ok::<(), flexon::Error>(())
+    /// ```
+    #[inline]
+    pub fn iter_array<V: ValueBuilder<'a, S>>(&mut self) -> ArrayStream<'_, 'a, S, C, V> {
+        ArrayStream::new(self)
+    }
+
+    /// Returns the byte offset the parser is currently at.
+    ///
+    /// After a failure, it points at the byte where parsing stopped, the same offset the
+    /// returned [`Error`](crate::Error) carries.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{ErrorKind, Parser, Value};
+    ///
+    /// let mut parser = Parser::from_str(r#"{"a": [1, 2 3]}"#);
+    /// let res: Result<Value, _> = parser.parse();
+    ///
+    /// assert_eq!(res.unwrap_err(), ErrorKind::UnexpectedToken);
+    /// assert_eq!(parser.offset(), 12);
+    /// ```
+    #[inline]
+    pub fn offset(&mut self) -> usize {
+        let tmp = unsafe {
+            match S::NULL_PADDED {
+                true => self.cur.ptr.addr().wrapping_sub(self.src.ptr(0).addr()),
+                _ => self.cur.idx,
             }
+        };
+
+        // the parser starts right before the source
+        match tmp {
+            usize::MAX => 0,
+            v => v,
         }
     }
 
@@ -283,6 +481,16 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         }
     }
 
+    #[inline(always)]
+    pub(crate) fn rewind(&mut self, n: usize) {
+        unsafe {
+            match S::NULL_PADDED {
+                true => self.cur.ptr = self.cur.ptr.sub(n),
+                _ => self.cur.idx = self.cur.idx.wrapping_sub(n),
+            }
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn dec_if_not_empty(&mut self) {
         unsafe {
@@ -304,6 +512,29 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         }
     }
 
+    #[inline]
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    pub(crate) fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            cur: self.cur,
+            depth: self.depth,
+            allocated: self.allocated,
+            #[cfg(feature = "comment")]
+            comments: self.comments.len(),
+        }
+    }
+
+    // the source must still hold the data after the checkpoint.
+    #[inline]
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    pub(crate) fn restore(&mut self, v: Checkpoint) {
+        self.cur = v.cur;
+        self.depth = v.depth;
+        self.allocated = v.allocated;
+        #[cfg(feature = "comment")]
+        self.comments.truncate(v.comments);
+    }
+
     #[inline(always)]
     pub(crate) fn cur_ptr(&mut self) -> *const u8 {
         unsafe {
@@ -346,44 +577,68 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
 
             if !matches!(tmp, b' ' | b'\t' | b'\n' | b'\r') {
                 #[cfg(feature = "comment")]
-                if tmp == b'/' && self.cfg.comments() {
+                if self.is_comment(tmp) {
                     self.comment();
                     continue;
                 }
 
+                if self.dialect_space(tmp) {
+                    continue;
+                }
+
                 return tmp;
             }
 
             if fast && self.simd_wh() {
+                let tmp = self.cur();
+
                 #[cfg(feature = "comment")]
-                if self.cur() == b'/' && self.cfg.comments() {
+                if self.is_comment(tmp) {
                     self.comment();
                     continue;
                 }
 
-                return self.cur();
+                if self.dialect_space(tmp) {
+                    continue;
+                }
+
+                return tmp;
             }
 
             fast = true;
         }
     }
 
+    #[inline(always)]
+    #[cfg(feature = "comment")]
+    fn is_comment(&self, c: u8) -> bool {
+        let syntax = self.cfg.comments();
+        c == b'/' && syntax.contains(CommentSyntax::SLASH)
+            || c == b'#' && syntax.contains(CommentSyntax::HASH)
+    }
+
     #[cfg(feature = "comment")]
     fn comment(&mut self) {
+        // `#` is the whole prefix, unlike `//` and `/*`.
+        let hash = self.cur() == b'#';
+
         // i dont think its worth adding simd here
-        if match S::NULL_PADDED {
-            true => unsafe { *self.cur_ptr().add(1) == 0 },
-            _ => self.idx() + 1 == self.src.len(),
-        } {
-            return;
+        if !hash {
+            if match S::NULL_PADDED {
+                true => unsafe { *self.cur_ptr().add(1) == 0 },
+                _ => self.idx() + 1 == self.src.len(),
+            } {
+                return;
+            }
+
+            self.inc(1);
         }
 
-        self.inc(1);
         let mut multi = false;
         let stamp = self.idx() + 1;
 
         match self.cur() {
-            b'/' => loop {
+            v if hash || v == b'/' => loop {
                 if match S::NULL_PADDED {
                     true => unsafe { *self.cur_ptr().add(1) == 0 },
                     _ => self.idx() + 1 == self.src.len(),
@@ -434,7 +689,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
             // omits checking non zero len when (de)allocating
             S::Volatility::IS_VOLATILE && len != 0,
             #[cfg(feature = "span")]
-            [stamp - 2, idx - !multi as usize],
+            [stamp + hash as usize - 2, idx - !multi as usize],
         ))
     }
 
@@ -447,7 +702,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
 
         unsafe {
             match self.skip_whitespace() {
-                b'"' => self.string::<_, V::String, V::Error>(),
+                v if self.is_quote(v) => self.string::<_, V::String, V::Error>(),
                 b'{' => self.object(),
                 b'[' => self.array(),
                 0 => {
@@ -464,6 +719,10 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
 
     #[allow(unused_mut)]
     unsafe fn object<V: ValueBuilder<'a, S>>(&mut self) -> Result<V, V::Error> {
+        if self.enter() {
+            return Err(self.depth_error());
+        }
+
         #[cfg(feature = "span")]
         let start = self.idx();
         #[cfg(feature = "prealloc")]
@@ -473,6 +732,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         let mut tmp = self.skip_whitespace();
 
         if tmp == b'}' {
+            self.leave();
             obj.on_complete();
             let mut tmp = obj.into();
 
@@ -481,17 +741,42 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
             return Ok(tmp);
         }
 
+        let mut len = 0;
         let mut err = loop {
-            if tmp != b'"' {
+            if !self.is_key(tmp) {
                 break V::Error::unexpected_token();
             }
 
-            let key = self.string::<V::String, V::String, V::Error>()?;
+            len += 1;
+            if unlikely(len > self.cfg.max_container_len()) {
+                break V::Error::container_too_large();
+            }
+
+            if self.allocate(size_of::<(V::String, V)>()) {
+                break V::Error::alloc_limit_exceeded();
+            }
+
+            let key_start = self.idx();
+            let key = self.key::<V::String, V::String, V::Error>()?;
+            let dup = match self.cfg.duplicate_keys() {
+                DuplicateKeys::Allow => None,
+                v => obj.find(&key).map(|idx| (idx, v)),
+            };
+
+            if let Some((_, DuplicateKeys::Reject)) = dup {
+                return Err(self.string_error(V::Error::duplicate_key(), key_start));
+            }
+
             if self.skip_whitespace() != b':' {
                 break V::Error::expected_colon();
             }
 
-            obj.on_value(key, self.value()?);
+            let val = self.value()?;
+            match dup {
+                Some((idx, DuplicateKeys::LastWins)) => obj.replace(idx, val),
+                Some(_) => {}
+                None => obj.on_value(key, val),
+            }
             tmp = self.skip_whitespace();
             let comma = tmp == b',';
             if comma {
@@ -500,6 +785,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
 
             if tmp == b'}' {
                 if !comma || self.cfg.trailing_comma() {
+                    self.leave();
                     obj.on_complete();
 
                     #[cfg(feature = "prealloc")]
@@ -535,12 +821,17 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
 
     #[allow(unused_mut)]
     unsafe fn array<V: ValueBuilder<'a, S>>(&mut self) -> Result<V, V::Error> {
+        if self.enter() {
+            return Err(self.depth_error());
+        }
+
         #[cfg(feature = "span")]
         let start = self.idx();
         let mut arr = V::Array::new();
         let mut tmp = self.skip_whitespace();
 
         if tmp == b']' {
+            self.leave();
             arr.on_complete();
             let mut tmp = arr.into();
 
@@ -549,9 +840,19 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
             return Ok(tmp);
         }
 
+        let mut len = 0;
         let mut err = loop {
+            len += 1;
+            if unlikely(len > self.cfg.max_container_len()) {
+                break V::Error::container_too_large();
+            }
+
+            if self.allocate(size_of::<V>()) {
+                break V::Error::alloc_limit_exceeded();
+            }
+
             arr.on_value(match tmp {
-                b'"' => self.string::<_, V::String, _>(),
+                v if self.is_quote(v) => self.string::<_, V::String, _>(),
                 b'{' => self.object(),
                 b'[' => self.array(),
                 0 => {
@@ -572,6 +873,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
 
             if tmp == b']' {
                 if !comma || self.cfg.trailing_comma() {
+                    self.leave();
                     arr.on_complete();
                     let mut tmp = arr.into();
 
@@ -601,17 +903,23 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         Err(err)
     }
 
-    unsafe fn string<T, V, E>(&mut self) -> Result<T, E>
+    pub(crate) unsafe fn string<T, V, E>(&mut self) -> Result<T, E>
     where
         V: StringBuilder<'a, S, E> + Into<T>,
         E: ErrorBuilder,
     {
         let start = self.idx();
+        let quote = self.cur();
         let mut offset = start + 1;
         let mut buf = V::new();
         let end = 'main: {
             let err = loop {
-                if self.simd_str() {
+                if self.string_too_long(start) {
+                    return Err(self.string_error(E::string_too_long(), start));
+                }
+
+                // the simd routines only look for '"'
+                if quote == b'"' && self.simd_str() {
                     continue;
                 }
 
@@ -621,7 +929,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
                 }
 
                 break match self.cur() {
-                    b'"' => break 'main self.idx(),
+                    v if v == quote => break 'main self.idx(),
                     b'\\' => {
                         buf.on_chunk(from_raw_parts(self.src.ptr(offset), self.idx() - offset));
 
@@ -640,9 +948,11 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
                             continue;
                         }
 
-                        if tmp == b'u'
-                            && let Some(v) = self.unicode_escape(&mut [0; 4])
-                        {
+                        let mut esc = [0; 4];
+                        if let Some(v) = match tmp {
+                            b'u' => self.unicode_escape(&mut esc),
+                            _ => self.dialect_escape(&mut esc),
+                        } {
                             offset = self.idx() + 1;
                             buf.on_escape(v);
                             continue;
@@ -663,13 +973,22 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
                 };
             };
             #[allow(unused_mut)]
-            let mut err = self.close_string(err);
+            let mut err = self.close_string(quote, err);
 
             #[cfg(feature = "span")]
             err.apply_span(start, self.idx());
 
             return Err(err);
         };
+        // the limit is only checked per chunk inside the loop
+        if self.string_too_long(start + 1) {
+            return Err(self.string_error(E::string_too_long(), start));
+        }
+
+        if self.allocate(end - start - 1) {
+            return Err(self.string_error(E::alloc_limit_exceeded(), start));
+        }
+
         let raw = from_raw_parts(self.src.ptr(start + 1), end - start - 1);
 
         if !S::UTF8 {
@@ -748,9 +1067,86 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         char::from_u32(codepoint).map(|v| v.encode_utf8(buf).as_bytes())
     }
 
+    /// Increments the nesting depth, returns `true` if it exceeds the limit.
+    #[inline(always)]
+    pub(crate) fn enter(&mut self) -> bool {
+        self.depth += 1;
+        unlikely(self.depth > self.cfg.max_depth())
+    }
+
+    #[inline(always)]
+    pub(crate) fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[allow(unused_mut)]
+    pub(crate) fn depth_error<E: ErrorBuilder>(&mut self) -> E {
+        let mut err = E::depth_limit_exceeded();
+        #[cfg(feature = "span")]
+        err.apply_span(self.idx(), self.idx());
+        err
+    }
+
+    /// Whether the string starting at the given offset exceeds the length limit so far.
+    #[inline(always)]
+    pub(crate) fn string_too_long(&mut self, start: usize) -> bool {
+        unlikely(self.idx().wrapping_sub(start) > self.cfg.max_string_len())
+    }
+
+    /// Accounts the given amount of bytes, returns `true` if it exceeds the allocation limit.
+    #[inline(always)]
+    pub(crate) fn allocate(&mut self, n: usize) -> bool {
+        if self.cfg.max_alloc() == usize::MAX {
+            return false;
+        }
+
+        self.allocated = self.allocated.saturating_add(n);
+        unlikely(self.allocated > self.cfg.max_alloc())
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[allow(unused_mut, unused_variables)]
+    fn string_error<E: ErrorBuilder>(&mut self, mut err: E, start: usize) -> E {
+        #[cfg(feature = "span")]
+        err.apply_span(start, self.idx());
+        err
+    }
+
+    /// Finishes the given error before it is returned to the user.
+    ///
+    /// Replaces it with the source's I/O error, if there is any, and records where parsing stopped.
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn fail<E: ErrorBuilder>(&mut self, err: E) -> E {
+        if let Some(v) = self.take_io_error() {
+            return v;
+        }
+
+        let mut err = err;
+        err.apply_offset(self.offset());
+        err
+    }
+
+    #[cold]
+    pub(crate) fn take_io_error<E: ErrorBuilder>(&mut self) -> Option<E> {
+        #[cfg(feature = "std")]
+        if let Some(v) = self.src.take_error() {
+            let mut err = E::io(v);
+            #[cfg(feature = "span")]
+            err.apply_span(self.idx(), self.idx());
+            err.apply_offset(self.offset());
+            return Some(err);
+        }
+
+        None
+    }
+
     #[cold]
     #[inline(never)]
-    pub(crate) fn close_string<E: ErrorBuilder>(&mut self, with: E) -> E {
+    pub(crate) fn close_string<E: ErrorBuilder>(&mut self, quote: u8, with: E) -> E {
         let mut flag = true;
 
         loop {
@@ -763,7 +1159,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
 
             self.inc(1);
             flag = match self.cur() {
-                b'"' if flag => return with,
+                v if v == quote && flag => return with,
                 v => v != b'\\',
             };
         }
@@ -773,16 +1169,24 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
     #[allow(unused_mut)]
     unsafe fn literal<V: ValueBuilder<'a, S>>(&mut self) -> Result<V, V::Error> {
         if V::CUSTOM_LITERAL {
+            // `#` only ends a literal when it starts a comment, which the simd routine
+            // does not look for.
+            #[cfg(feature = "comment")]
+            let hash = self.cfg.comments().contains(CommentSyntax::HASH);
+            #[cfg(not(feature = "comment"))]
+            let hash = false;
+
             let start = self.idx();
             let end = loop {
                 if !S::NULL_PADDED && self.idx() + 1 >= self.src.len()
                     || NON_LIT_LUT[self.cur() as usize]
+                    || hash && self.cur() == b'#'
                 {
                     break self.idx();
                 }
 
                 self.inc(1);
-                if self.simd_lit() {
+                if !hash && self.simd_lit() {
                     break self.idx();
                 }
             };
@@ -804,6 +1208,22 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         let stamp = self.idx();
         let tmp = self.cur();
 
+        if self.is_number(tmp) {
+            let mut tmp = match self.number::<V::Error>() {
+                Ok(Number::Int(val, neg)) => V::integer(val, neg),
+                Ok(Number::Float(val)) => V::float(val),
+                Err(mut err) => {
+                    #[cfg(feature = "span")]
+                    err.apply_span(stamp, self.idx());
+                    return Err(err);
+                }
+            };
+
+            #[cfg(feature = "span")]
+            tmp.apply_span(stamp, self.idx());
+            return Ok(tmp);
+        }
+
         if NUM_LUT[tmp as usize] {
             let neg = tmp == b'-';
             if neg {
@@ -908,6 +1328,9 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
                 };
             };
 
+            // point at the start of the literal
+            self.rewind(3);
+
             #[cfg(feature = "span")]
             err.apply_span(stamp, stamp);
             return Err(err);
@@ -1113,3 +1536,225 @@ impl<R: Read> Parser<'_, Reader<true, R>> {
         Self::new(Reader::new_unchecked(r))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Parser;
+    use crate::{ErrorKind, config::RTConfig};
+
+    // lengths around the limit and across the simd chunk boundaries.
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn string_limit_across_chunks() {
+        use crate::Value;
+
+        for len in 0..200 {
+            let src = alloc::format!("\"{}\"", "a".repeat(len));
+            let cfg = RTConfig::new().max_string_len(10);
+            let res = Parser::new_with(src.as_str(), cfg).parse::<Value>();
+
+            match len > 10 {
+                true => assert_eq!(res.unwrap_err(), ErrorKind::StringTooLong, "{len}"),
+                _ => assert!(res.is_ok(), "{len}"),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn lazy_with_dialect() {
+        use crate::{LazyValue, config::Dialect};
+
+        let cfg = RTConfig::new().dialect(Dialect::JSON5);
+        let val: LazyValue = Parser::new_with(r#"{"a": [1, 2]}"#, cfg.clone())
+            .parse()
+            .unwrap();
+        assert_eq!(
+            val.pointer(crate::jsonp!["a", 1]).unwrap().as_u64(),
+            Some(2)
+        );
+
+        let mut parser = Parser::new_with(r#"{"a": [1, 2], b: 3}"#, cfg);
+        let res = parser.parse::<LazyValue>();
+        assert_eq!(res.unwrap_err(), ErrorKind::UnexpectedToken);
+        assert_eq!(parser.offset(), 14);
+
+        #[cfg(feature = "comment")]
+        {
+            use crate::config::CTConfig;
+
+            let cfg = CTConfig::new().allow_comments();
+            let res = Parser::new_with("[1, 2]", cfg).parse::<LazyValue>();
+            assert!(res.is_ok());
+
+            let res = Parser::new_with("[1, /* 2 */ 3]", cfg).parse::<LazyValue>();
+            assert_eq!(res.unwrap_err(), ErrorKind::UnexpectedToken);
+        }
+    }
+
+    // yields the given data, then fails.
+    #[cfg(feature = "std")]
+    struct Broken(&'static [u8]);
+
+    #[cfg(feature = "std")]
+    impl std::io::Read for Broken {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0.is_empty() {
+                return Err(std::io::ErrorKind::BrokenPipe.into());
+            }
+
+            let len = self.0.len().min(buf.len());
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn io_error_after_scalar() {
+        use crate::OwnedValue;
+
+        let res = Parser::from_reader(Broken(b"12345")).parse::<OwnedValue>();
+        assert_eq!(
+            res.unwrap_err(),
+            ErrorKind::Io(std::io::ErrorKind::BrokenPipe.into())
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            use crate::serde::de::Kind;
+
+            let res = crate::from_reader::<_, u64>(Broken(b"12345"));
+            assert!(matches!(res.unwrap_err().kind(), Kind::Io(_)));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "std"))]
+    fn serde_string_limit_across_chunks() {
+        use crate::{serde::de::Kind, source::Reader};
+        use serde::Deserialize;
+
+        fn check<'a, S: super::Source>(mut parser: Parser<'a, S, RTConfig>, len: usize) {
+            let res = alloc::string::String::deserialize(&mut parser);
+            match len > 10 {
+                true => assert!(
+                    matches!(res.unwrap_err().kind(), Kind::StringTooLong),
+                    "{len}"
+                ),
+                _ => assert!(res.is_ok(), "{len}"),
+            }
+        }
+
+        for len in 0..200 {
+            let mut src = alloc::format!("\"{}\"", "a".repeat(len));
+            let cfg = RTConfig::new().max_string_len(10);
+
+            check(Parser::new_with(src.as_str(), cfg.clone()), len);
+            check(
+                Parser::new_with(Reader::new(src.as_bytes()), cfg.clone()),
+                len,
+            );
+            check(Parser::new_with(src.as_mut_str(), cfg), len);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn duplicate_keys_in_large_objects() {
+        use crate::{Value, config::DuplicateKeys};
+        use alloc::{format, string::String, vec::Vec};
+
+        let members: Vec<String> = (0..200).map(|i| format!("\"k{}\": {i}", i % 100)).collect();
+        let src = format!("{{{}}}", members.join(", "));
+        let cfg = |v| RTConfig::new().duplicate_keys(v);
+
+        let val: Value = Parser::new_with(src.as_str(), cfg(DuplicateKeys::FirstWins))
+            .parse()
+            .unwrap();
+        let obj = val.as_object().unwrap();
+        assert_eq!((obj.len(), obj["k5"].as_u64()), (100, Some(5)));
+
+        let val: Value = Parser::new_with(src.as_str(), cfg(DuplicateKeys::LastWins))
+            .parse()
+            .unwrap();
+        let obj = val.as_object().unwrap();
+        assert_eq!((obj.len(), obj["k5"].as_u64()), (100, Some(105)));
+
+        let res = Parser::new_with(src.as_str(), cfg(DuplicateKeys::Reject)).parse::<Value>();
+        assert_eq!(res.unwrap_err(), ErrorKind::DuplicateKey);
+
+        #[cfg(all(feature = "serde", feature = "std"))]
+        {
+            use serde::Deserialize;
+            use std::collections::HashMap;
+
+            let map = |v| {
+                HashMap::<String, u64>::deserialize(&mut Parser::new_with(src.as_str(), cfg(v)))
+            };
+
+            let val = map(DuplicateKeys::FirstWins).unwrap();
+            assert_eq!((val.len(), val["k5"]), (100, 5));
+
+            let val = map(DuplicateKeys::LastWins).unwrap();
+            assert_eq!((val.len(), val["k5"]), (100, 105));
+
+            assert!(map(DuplicateKeys::Reject).is_err());
+        }
+    }
+
+    #[test]
+    fn validate_applies_limits() {
+        let validate = |src, cfg| Parser::new_with(src, cfg).validate();
+        let long = RTConfig::new().max_string_len(3);
+        let large = RTConfig::new().max_container_len(2);
+
+        assert!(validate(r#"["abc", {"abc": 1}]"#, long.clone()).is_ok());
+        let err = validate(r#"["abcd"]"#, long.clone()).unwrap_err();
+        assert_eq!(err, ErrorKind::StringTooLong);
+        let err = validate(r#"{"abcd": 1}"#, long).unwrap_err();
+        assert_eq!(err, ErrorKind::StringTooLong);
+
+        assert!(validate(r#"[[1, 2], {"a": 1, "b": 2}]"#, large.clone()).is_ok());
+        let err = validate("[1, 2, 3]", large.clone()).unwrap_err();
+        assert_eq!(err, ErrorKind::ContainerTooLarge);
+        let err = validate(r#"[{"a": 1, "b": 2, "c": 3}]"#, large).unwrap_err();
+        assert_eq!(err, ErrorKind::ContainerTooLarge);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn json5_identifiers_and_whitespace() {
+        use crate::{Value, config::Dialect};
+
+        let cfg = RTConfig::new().dialect(Dialect::JSON5);
+        let src = "\u{FEFF}{café:\u{A0}1,\u{2028}a\u{301}_$:\u{3000}[2\u{B}\u{C}]}";
+
+        let val: Value = Parser::new_with(src, cfg.clone()).parse().unwrap();
+        assert_eq!(val["café"].as_u64(), Some(1));
+        assert_eq!(val["a\u{301}_$"][0].as_u64(), Some(2));
+        assert_eq!(Parser::new_with(src, cfg.clone()).validate(), Ok(()));
+
+        let res = Parser::new_with("{\u{301}a: 1}", cfg.clone()).parse::<Value>();
+        assert_eq!(res.unwrap_err(), ErrorKind::UnexpectedToken);
+        let res = Parser::new_with("{a→b: 1}", cfg).parse::<Value>();
+        assert_eq!(res.unwrap_err(), ErrorKind::ExpectedColon);
+
+        let cfg = RTConfig::new().dialect(Dialect::UNQUOTED_KEYS);
+        let res = Parser::new_with("{a:\u{A0}1}", cfg).parse::<Value>();
+        assert_eq!(res.unwrap_err(), ErrorKind::InvalidLiteral);
+
+        #[cfg(all(feature = "serde", feature = "std"))]
+        {
+            use crate::source::Reader;
+            use serde::Deserialize;
+            use std::collections::HashMap;
+
+            let cfg = RTConfig::new().dialect(Dialect::JSON5);
+            let mut parser = Parser::new_with(Reader::new(src.as_bytes()), cfg);
+            let val = HashMap::<String, serde::de::IgnoredAny>::deserialize(&mut parser).unwrap();
+            assert!(val.contains_key("café") && val.contains_key("a\u{301}_$"));
+        }
+    }
+}
@@ -0,0 +1,521 @@
+use crate::{
+    Error, Parser,
+    config::Config,
+    source::{Source, Volatility},
+    value::builder::ErrorBuilder,
+};
+use core::ptr::copy;
+
+#[cfg(any(feature = "alloc", all(feature = "serde", feature = "std")))]
+use core::slice::from_raw_parts;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+#[cfg(all(feature = "serde", feature = "std"))]
+use {crate::serde::format::Format, std::io::Write};
+
+/// Where the rewritten output goes.
+trait Sink {
+    /// Copies the source bytes from `start` to `end` (inclusive).
+    unsafe fn copy<S: Source>(
+        &mut self,
+        src: &mut S,
+        start: usize,
+        end: usize,
+    ) -> Result<(), Error>;
+
+    /// Writes a byte that is not in the source, right before the byte at `next`.
+    unsafe fn insert<S: Source>(&mut self, src: &mut S, c: u8, next: usize) -> Result<(), Error>;
+
+    /// Called after an opening bracket.
+    #[inline(always)]
+    fn enter(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called before a closing bracket.
+    #[inline(always)]
+    fn leave(&mut self, _empty: bool) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called before each element or member.
+    #[inline(always)]
+    fn indent(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called after a colon.
+    #[inline(always)]
+    fn sep(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Sink for Vec<u8> {
+    #[inline(always)]
+    unsafe fn copy<S: Source>(
+        &mut self,
+        src: &mut S,
+        start: usize,
+        end: usize,
+    ) -> Result<(), Error> {
+        self.extend_from_slice(from_raw_parts(src.ptr(start), end + 1 - start));
+        Ok(())
+    }
+
+    #[inline(always)]
+    unsafe fn insert<S: Source>(&mut self, _: &mut S, c: u8, _: usize) -> Result<(), Error> {
+        self.push(c);
+        Ok(())
+    }
+}
+
+/// Writes the output over the already consumed part of the source.
+struct InPlace {
+    len: usize,
+    // everything from here on is untouched.
+    read: usize,
+}
+
+impl Sink for InPlace {
+    #[inline(always)]
+    unsafe fn copy<S: Source>(
+        &mut self,
+        src: &mut S,
+        start: usize,
+        end: usize,
+    ) -> Result<(), Error> {
+        let len = end + 1 - start;
+        if self.len != start {
+            let ptr = src.ptr_mut(0);
+            copy(ptr.add(start), ptr.add(self.len), len);
+        }
+
+        self.len += len;
+        self.read = end + 1;
+        Ok(())
+    }
+
+    #[inline(always)]
+    unsafe fn insert<S: Source>(&mut self, src: &mut S, c: u8, next: usize) -> Result<(), Error> {
+        // there's no room when nothing separates the values.
+        if self.len == next {
+            return Err(Error::unexpected_token());
+        }
+
+        *src.ptr_mut(self.len) = c;
+        self.len += 1;
+        // the byte was consumed whitespace, keeps the padding on failure in bounds.
+        self.read = self.read.max(self.len);
+        Ok(())
+    }
+}
+
+/// Writes the output into a writer, laid out by the given format.
+#[cfg(all(feature = "serde", feature = "std"))]
+struct Formatted<W, F>(W, F);
+
+#[cfg(all(feature = "serde", feature = "std"))]
+impl<W: Write, F: Format> Sink for Formatted<W, F> {
+    #[inline(always)]
+    unsafe fn copy<S: Source>(
+        &mut self,
+        src: &mut S,
+        start: usize,
+        end: usize,
+    ) -> Result<(), Error> {
+        self.0
+            .write_all(from_raw_parts(src.ptr(start), end + 1 - start))
+            .map_err(Error::io)
+    }
+
+    #[inline(always)]
+    unsafe fn insert<S: Source>(&mut self, _: &mut S, c: u8, _: usize) -> Result<(), Error> {
+        self.0.write_all(&[c]).map_err(Error::io)
+    }
+
+    #[inline(always)]
+    fn enter(&mut self) -> Result<(), Error> {
+        self.1.inc();
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn leave(&mut self, empty: bool) -> Result<(), Error> {
+        self.1.dec();
+        match empty {
+            true => Ok(()),
+            _ => self.1.indent(&mut self.0).map_err(Error::io),
+        }
+    }
+
+    #[inline(always)]
+    fn indent(&mut self) -> Result<(), Error> {
+        self.1.indent(&mut self.0).map_err(Error::io)
+    }
+
+    #[inline(always)]
+    fn sep(&mut self) -> Result<(), Error> {
+        self.1.sep(&mut self.0).map_err(Error::io)
+    }
+}
+
+/// Options for [`Parser::minify_with`] and [`Parser::minify_in_place_with`].
+///
+/// Comments are always dropped, as a line comment would need a line break after it and
+/// the output would no longer be a single line.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MinifyOptions {
+    trailing_comma: bool,
+}
+
+impl MinifyOptions {
+    /// Creates the default options, converting JSONC to strict JSON.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            trailing_comma: false,
+        }
+    }
+
+    /// Keeps the trailing commas instead of dropping them.
+    #[inline]
+    pub const fn keep_trailing_comma(mut self) -> Self {
+        self.trailing_comma = true;
+        self
+    }
+}
+
+impl<'a, S: Source, C: Config> Parser<'a, S, C> {
+    /// Minifies the source, dropping insignificant whitespace and comments.
+    ///
+    /// Trailing commas are dropped and missing ones are inserted, so JSONC accepted by the
+    /// configuration comes out as strict JSON, see [`Parser::minify_with`] to keep the trailing
+    /// commas. Other dialect extensions are kept as is. Like
+    /// [`Parser::validate`], the source must be a single valid JSON document.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{Parser, config::CTConfig};
+    ///
+    /// let src = "{\n  \"name\": \"flexon\",\n  \"tags\": [\"json\", \"simd\",],\n}";
+    /// let cfg = CTConfig::new().allow_trailing_comma();
+    ///
+    /// assert_eq!(
+    ///     Parser::new_with(src, cfg).minify()?,
+    ///     r#"{"name":"flexon","tags":["json","simd"]}"#,
+    /// );
+    /// # Ok::<(), flexon::Error>(())
+    /// ```
+    #[inline]
+    #[cfg(feature = "alloc")]
+    pub fn minify(&mut self) -> Result<String, Error> {
+        self.minify_with(MinifyOptions::new())
+    }
+
+    /// Minifies the source with the given options.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{MinifyOptions, Parser, config::CTConfig};
+    ///
+    /// let src = "[1, 2, 3,\n]";
+    /// let cfg = CTConfig::new().allow_trailing_comma();
+    /// let opts = MinifyOptions::new().keep_trailing_comma();
+    ///
+    /// assert_eq!(Parser::new_with(src, cfg).minify_with(opts)?, "[1,2,3,]");
+    /// # Ok::<(), flexon::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn minify_with(&mut self, opts: MinifyOptions) -> Result<String, Error> {
+        let mut out = Vec::new();
+        self.rewrite(&mut out, opts)?;
+
+        // strings and keys are validated, the rest is ascii.
+        Ok(unsafe { String::from_utf8_unchecked(out) })
+    }
+
+    /// Minifies the source in place, returning the length of the output.
+    ///
+    /// Same as [`Parser::minify`] except that the output is written at the start of the source
+    /// and the rest is filled with spaces. The source must allow in-situ parsing.
+    ///
+    /// A missing comma can only be inserted when something separated the values, e.g. `[1 2]`.
+    /// Otherwise, as in `[{}{}]`, there is no room for it and it results in
+    /// [`UnexpectedToken`](crate::ErrorKind::UnexpectedToken) error.
+    ///
+    /// Comments are discarded as the source they point to is overwritten. On failure, the
+    /// source is left partially minified, though it stays valid UTF-8.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::Parser;
+    ///
+    /// let mut buf = *b"[1, 2, {\"a\": null}]";
+    /// let len = Parser::from_mut_slice(&mut buf).minify_in_place()?;
+    ///
+    /// assert_eq!(&buf[..len], br#"[1,2,{"a":null}]"#);
+    /// # Ok::<(), flexon::Error>(())
+    /// ```
+    #[inline]
+    pub fn minify_in_place(&mut self) -> Result<usize, Error> {
+        self.minify_in_place_with(MinifyOptions::new())
+    }
+
+    /// Minifies the source in place with the given options, returning the length of the output.
+    ///
+    /// Same as [`Parser::minify_in_place`], see [`Parser::minify_with`].
+    pub fn minify_in_place_with(&mut self, opts: MinifyOptions) -> Result<usize, Error> {
+        const {
+            assert!(S::INSITU, "the source must allow in-situ parsing");
+        }
+
+        let mut out = InPlace { len: 0, read: 0 };
+        let res = self.rewrite(&mut out, opts);
+        let end = match res {
+            Ok(_) => self.idx().wrapping_add(1),
+            _ => out.read,
+        };
+
+        unsafe { self.src.ptr_mut(out.len).write_bytes(b' ', end - out.len) }
+
+        #[cfg(feature = "comment")]
+        self.comments.clear();
+
+        res.map(|_| out.len)
+    }
+
+    /// Reformats the source token by token using the given format.
+    ///
+    /// Keys, strings and numbers are copied as they are spelled in the source, only the
+    /// layout changes. Comments and trailing commas are dropped like in [`Parser::minify`].
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{Parser, serde::format::Pretty};
+    ///
+    /// let out = Parser::from_str(r#"{"pi": 3.140, "e": [2.7e0]}"#).reformat(Pretty::new())?;
+    ///
+    /// assert_eq!(out, "{\n  \"pi\": 3.140,\n  \"e\": [\n    2.7e0\n  ]\n}");
+    /// # Ok::<(), flexon::Error>(())
+    /// ```
+    #[cfg(all(feature = "serde", feature = "std"))]
+    pub fn reformat<F: Format>(&mut self, f: F) -> Result<String, Error> {
+        let mut out = Vec::new();
+        self.reformat_to_writer(&mut out, f)?;
+
+        // same as `Parser::minify`, the indentation is a `&str`.
+        Ok(unsafe { String::from_utf8_unchecked(out) })
+    }
+
+    /// Reformats the source token by token into the given writer.
+    ///
+    /// Same as [`Parser::reformat`], but the output is streamed. Volatile sources such as
+    /// [`Reader`](crate::source::Reader) are trimmed as the tokens are written, so the
+    /// document is never held in memory as a whole.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{Parser, serde::format::Pretty, source::Reader};
+    ///
+    /// let src = Reader::new(r#"[1, {"a": null}]"#.as_bytes());
+    /// let mut out = Vec::new();
+    ///
+    /// Parser::new(src).reformat_to_writer(&mut out, Pretty::with_indent("\t"))?;
+    /// assert_eq!(out, b"[\n\t1,\n\t{\n\t\t\"a\": null\n\t}\n]");
+    /// # Ok::<(), flexon::Error>(())
+    /// ```
+    #[cfg(all(feature = "serde", feature = "std"))]
+    pub fn reformat_to_writer<W: Write, F: Format>(&mut self, w: W, f: F) -> Result<(), Error> {
+        self.rewrite(&mut Formatted(w, f), MinifyOptions::new())
+    }
+
+    fn rewrite<O: Sink>(&mut self, out: &mut O, opts: MinifyOptions) -> Result<(), Error> {
+        let tmp = self.skip_whitespace();
+        if let Err(err) = self.rewrite_value(tmp, out, opts) {
+            return Err(self.fail(err));
+        }
+
+        match self.skip_whitespace() {
+            0 => self.take_io_error().map_or(Ok(()), Err),
+            _ => Err(self.fail(Error::unexpected_token())),
+        }
+    }
+
+    #[inline(always)]
+    fn emit<O: Sink>(&mut self, out: &mut O, start: usize, end: usize) -> Result<(), Error> {
+        unsafe { out.copy(&mut self.src, start, end)? };
+
+        if S::Volatility::IS_VOLATILE {
+            self.src.trim(end + 1);
+        }
+
+        Ok(())
+    }
+
+    fn rewrite_value<O: Sink>(
+        &mut self,
+        tmp: u8,
+        out: &mut O,
+        opts: MinifyOptions,
+    ) -> Result<(), Error> {
+        let start = self.idx();
+        match tmp {
+            v if self.is_quote(v) => self.skip_string(),
+            b'{' | b'[' => return self.rewrite_container(tmp, out, opts),
+            0 => Err(Error::expected_value()),
+            _ => unsafe { self.skip_literal() },
+        }?;
+
+        let end = self.idx();
+        self.emit(out, start, end)
+    }
+
+    fn rewrite_container<O: Sink>(
+        &mut self,
+        open: u8,
+        out: &mut O,
+        opts: MinifyOptions,
+    ) -> Result<(), Error> {
+        if self.enter() {
+            return Err(self.depth_error());
+        }
+
+        // '}' and ']' are two bytes after their opening counterparts.
+        let close = open + 2;
+        let is_obj = open == b'{';
+        let start = self.idx();
+        self.emit(out, start, start)?;
+        out.enter()?;
+
+        let mut tmp = self.skip_whitespace();
+        let mut empty = true;
+        let err = loop {
+            if tmp == close {
+                self.leave();
+                out.leave(empty)?;
+                let end = self.idx();
+                return self.emit(out, end, end);
+            }
+
+            empty = false;
+            out.indent()?;
+
+            if is_obj {
+                if !self.is_key(tmp) {
+                    break Error::unexpected_token();
+                }
+
+                let start = self.idx();
+                self.skip_key::<Error>()?;
+                let end = self.idx();
+                self.emit(out, start, end)?;
+
+                if self.skip_whitespace() != b':' {
+                    break Error::expected_colon();
+                }
+
+                let colon = self.idx();
+                self.emit(out, colon, colon)?;
+                out.sep()?;
+                tmp = self.skip_whitespace();
+            }
+
+            match tmp {
+                0 if !is_obj => return Err(Error::eof()),
+                _ => self.rewrite_value(tmp, out, opts)?,
+            }
+
+            tmp = self.skip_whitespace();
+            let comma = tmp == b',';
+            let at = self.idx();
+
+            if comma {
+                tmp = self.skip_whitespace();
+            }
+
+            if tmp == close {
+                if !comma {
+                    continue;
+                }
+
+                if self.cfg.trailing_comma() {
+                    if opts.trailing_comma {
+                        self.emit(out, at, at)?;
+                    }
+
+                    continue;
+                }
+
+                break Error::trailing_comma();
+            }
+
+            if comma {
+                self.emit(out, at, at)?;
+                continue;
+            }
+
+            if self.cfg.comma() {
+                let next = self.idx();
+                unsafe { out.insert(&mut self.src, b',', next)? };
+                continue;
+            }
+
+            break match tmp {
+                0 => Error::eof(),
+                _ => Error::unexpected_token(),
+            };
+        };
+
+        Err(err)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{ErrorKind, Parser, config::CTConfig};
+    use alloc::vec::Vec;
+    use core::str::from_utf8_mut;
+
+    // an inserted comma advances the output past what was read.
+    #[test]
+    fn missing_comma_then_error() {
+        for src in ["[1 }", "[1 2 x]", "[1 2 3", "{\"a\": 1 \"b\" 2 ]"] {
+            let mut buf = src.as_bytes().to_vec();
+            let cfg = CTConfig::new().optional_comma();
+            let res = Parser::new_with(from_utf8_mut(&mut buf).unwrap(), cfg).minify_in_place();
+
+            assert!(res.is_err());
+            assert_eq!(buf.len(), src.len());
+            assert!(core::str::from_utf8(&buf).is_ok());
+        }
+
+        let mut buf: Vec<u8> = b"[1 2  3]".to_vec();
+        let cfg = CTConfig::new().optional_comma();
+        let len = Parser::new_with(from_utf8_mut(&mut buf).unwrap(), cfg).minify_in_place();
+
+        assert_eq!(&buf[..len.unwrap()], b"[1,2,3]");
+    }
+
+    // there is no room for the comma between adjacent values.
+    #[test]
+    fn missing_comma_without_room() {
+        for src in ["[{}{}]", "[[]\"a\"]", "{\"a\":{}\"b\":1}", "[1 {}[]]"] {
+            let mut buf = src.as_bytes().to_vec();
+            let cfg = CTConfig::new().optional_comma();
+            let res = Parser::new_with(from_utf8_mut(&mut buf).unwrap(), cfg).minify_in_place();
+
+            assert_eq!(res.unwrap_err(), ErrorKind::UnexpectedToken, "{src}");
+            assert!(core::str::from_utf8(&buf).is_ok());
+        }
+
+        let mut buf: Vec<u8> = b"[{} {}]".to_vec();
+        let cfg = CTConfig::new().optional_comma();
+        let len = Parser::new_with(from_utf8_mut(&mut buf).unwrap(), cfg).minify_in_place();
+
+        assert_eq!(&buf[..len.unwrap()], b"[{},{}]");
+    }
+}
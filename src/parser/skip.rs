@@ -5,7 +5,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
     #[inline]
     pub(crate) fn skip_value<E: ErrorBuilder>(&mut self) -> Result<(), E> {
         match self.skip_whitespace() {
-            b'"' => self.skip_string(),
+            v if self.is_quote(v) => self.skip_string(),
             b'{' => self.skip_object(),
             b'[' => self.skip_array(),
             0 => Err(E::expected_value()),
@@ -24,17 +24,28 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
     }
 
     pub(super) fn skip_object<E: ErrorBuilder>(&mut self) -> Result<(), E> {
+        if self.enter() {
+            return Err(self.depth_error());
+        }
+
         let mut tmp = self.skip_whitespace();
         if tmp == b'}' {
+            self.leave();
             return Ok(());
         }
 
+        let mut len = 0;
         let err = loop {
-            if tmp != b'"' {
+            if !self.is_key(tmp) {
                 break E::unexpected_token();
             }
 
-            self.skip_string()?;
+            len += 1;
+            if unlikely(len > self.cfg.max_container_len()) {
+                break E::container_too_large();
+            }
+
+            self.skip_key()?;
             if self.skip_whitespace() != b':' {
                 break E::expected_colon();
             }
@@ -48,6 +59,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
 
             if tmp == b'}' {
                 if !comma || self.cfg.trailing_comma() {
+                    self.leave();
                     return Ok(());
                 }
 
@@ -69,15 +81,25 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
     }
 
     pub(super) fn skip_array<E: ErrorBuilder>(&mut self) -> Result<(), E> {
-        let mut tmp = self.skip_whitespace();
+        if self.enter() {
+            return Err(self.depth_error());
+        }
 
+        let mut tmp = self.skip_whitespace();
         if tmp == b']' {
+            self.leave();
             return Ok(());
         }
 
+        let mut len = 0;
         let err = loop {
+            len += 1;
+            if unlikely(len > self.cfg.max_container_len()) {
+                break E::container_too_large();
+            }
+
             match tmp {
-                b'"' => self.skip_string(),
+                v if self.is_quote(v) => self.skip_string(),
                 b'{' => self.skip_object(),
                 b'[' => self.skip_array(),
                 0 => return Err(E::eof()),
@@ -92,6 +114,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
 
             if tmp == b']' {
                 if !comma || self.cfg.trailing_comma() {
+                    self.leave();
                     return Ok(());
                 }
 
@@ -112,10 +135,12 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         Err(err)
     }
 
-    pub(super) fn skip_string<E: ErrorBuilder>(&mut self) -> Result<(), E> {
+    pub(crate) fn skip_string<E: ErrorBuilder>(&mut self) -> Result<(), E> {
         let start = self.idx() + 1;
+        let quote = self.cur();
         let err = loop {
-            if self.simd_str() {
+            // the simd routines only look for '"'
+            if quote == b'"' && self.simd_str() {
                 continue;
             }
 
@@ -125,7 +150,11 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
             }
 
             break match self.cur() {
-                b'"' => unsafe {
+                v if v == quote => unsafe {
+                    if self.string_too_long(start) {
+                        return Err(self.string_error(E::string_too_long(), start - 1));
+                    }
+
                     return match S::UTF8
                         || simdutf8::basic::from_utf8(from_raw_parts(
                             self.src.ptr(start),
@@ -150,7 +179,10 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
                         continue;
                     }
 
-                    if tmp == b'u' && self.skip_unicode_escape() {
+                    if match tmp {
+                        b'u' => self.skip_unicode_escape(),
+                        _ => self.dialect_escape(&mut [0; 4]).is_some(),
+                    } {
                         continue;
                     }
 
@@ -162,7 +194,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
             };
         };
 
-        return Err(self.close_string(err));
+        return Err(self.close_string(quote, err));
     }
 
     // typically this function rarely gets called so not worth complicating
@@ -237,6 +269,10 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
     pub(super) unsafe fn skip_literal<E: ErrorBuilder>(&mut self) -> Result<(), E> {
         let tmp = self.cur();
 
+        if self.is_number(tmp) {
+            return self.number().map(|_| ());
+        }
+
         if NUM_LUT[tmp as usize] {
             let neg = tmp == b'-';
             if neg {
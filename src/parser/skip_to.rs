@@ -1,3 +1,5 @@
+#[cfg(feature = "alloc")]
+use crate::Error;
 use crate::{
     Parser,
     config::Config,
@@ -7,7 +9,7 @@ use crate::{
     utf8,
     value::builder::ErrorBuilder,
 };
-use core::hint::unreachable_unchecked;
+use core::{hint::unreachable_unchecked, slice::from_raw_parts};
 
 // ehh... used in the string matching functions below to just keep
 // reading and make their subsequent matches invalid once invalidated.
@@ -24,6 +26,8 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
     ///
     /// This will return early as soon as it reaches the specified path.
     /// If the JSON is invalid or path does not exist, returns error.
+    /// Parsers over a [`StructuralIndex`](crate::index::StructuralIndex) jump through the
+    /// index instead of scanning.
     ///
     /// # Example
     /// ```
@@ -44,7 +48,10 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         P: IntoIterator,
         P::Item: JsonPointer,
     {
-        self._skip_to(p)?;
+        if let Err(e) = self._skip_to(p) {
+            return Err(self.fail(e));
+        }
+
         self.dec_if_not_empty();
         Ok(())
     }
@@ -58,11 +65,42 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
     {
         let mut char = self.skip_whitespace();
 
+        // jump through the index instead of scanning if the source has one
+        #[cfg(feature = "alloc")]
+        let cur = self.idx();
+        #[cfg(feature = "alloc")]
+        if let Some(index) = self.src.index()
+            && let Some(idx) = index.locate(cur)
+        {
+            let (off, depth, res) = index.walk::<E, _>(cur, idx, p);
+
+            for _ in 0..depth {
+                if self.enter() {
+                    return Err(self.depth_error());
+                }
+            }
+
+            self.inc(off - cur);
+            return match res {
+                Ok(()) => Ok(self.cur()),
+                #[allow(unused_mut)]
+                Err(mut err) => {
+                    #[cfg(feature = "span")]
+                    err.apply_span(off, off);
+                    Err(err)
+                }
+            };
+        }
+
         'main: for pointer in p {
             #[allow(unused_mut)]
             let mut err = if let Some(key) = pointer.as_key()
                 && char == b'{'
             {
+                if self.enter() {
+                    return Err(self.depth_error());
+                }
+
                 #[cfg(feature = "span")]
                 let start = self.idx();
                 char = self.skip_whitespace();
@@ -76,11 +114,16 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
                 }
 
                 loop {
-                    if char != b'"' {
+                    if !self.is_key(char) {
                         break E::unexpected_token();
                     }
 
-                    let matches = unsafe { self.string_match(key)? };
+                    let matches = unsafe {
+                        match self.is_quote(char) {
+                            true => self.string_match(key)?,
+                            _ => self.ident_match(key)?,
+                        }
+                    };
                     if self.skip_whitespace() != b':' {
                         break E::expected_colon();
                     }
@@ -91,7 +134,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
                     }
 
                     match char {
-                        b'"' => self.skip_string(),
+                        v if self.is_quote(v) => self.skip_string(),
                         b'{' => self.skip_object(),
                         b'[' => self.skip_array(),
                         _ => unsafe { self.skip_literal() },
@@ -129,6 +172,10 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
             } else if let Some(mut idx) = pointer.as_index()
                 && char == b'['
             {
+                if self.enter() {
+                    return Err(self.depth_error());
+                }
+
                 #[cfg(feature = "span")]
                 let start = self.idx();
                 char = self.skip_whitespace();
@@ -148,7 +195,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
 
                     idx -= 1;
                     match char {
-                        b'"' => self.skip_string(),
+                        v if self.is_quote(v) => self.skip_string(),
                         b'{' => self.skip_object(),
                         b'[' => self.skip_array(),
                         _ => unsafe { self.skip_literal() },
@@ -199,6 +246,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
     }
 
     unsafe fn string_match<E: ErrorBuilder>(&mut self, target: &str) -> Result<bool, E> {
+        let quote = self.cur();
         let mut utf8 = utf8::Parser::new();
         let (mut start, end) = if target.is_empty() {
             (&raw const INVALID[1], &raw const INVALID[1])
@@ -213,7 +261,7 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
             }
 
             break match self.cur() {
-                b'"' => {
+                v if v == quote => {
                     if !S::UTF8 && utf8.is_expecting() {
                         break E::unexpected_token();
                     }
@@ -237,9 +285,11 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
                         continue;
                     }
 
-                    if cur == b'u'
-                        && let Some(esc) = self.unicode_escape(&mut [0; 4])
-                    {
+                    let mut esc = [0; 4];
+                    if let Some(esc) = match cur {
+                        b'u' => self.unicode_escape(&mut esc),
+                        _ => self.dialect_escape(&mut esc),
+                    } {
                         for &v in esc {
                             start = match start != end && *start == v {
                                 true => start.add(1),
@@ -268,6 +318,13 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
 
         Err(err)
     }
+
+    unsafe fn ident_match<E: ErrorBuilder>(&mut self, target: &str) -> Result<bool, E> {
+        let start = self.idx();
+        self.skip_ident()?;
+
+        Ok(from_raw_parts(self.src.ptr(start), self.idx() + 1 - start) == target.as_bytes())
+    }
 }
 
 impl<'a, S: Source<Volatility = NonVolatile>, C: Config> Parser<'a, S, C> {
@@ -292,6 +349,18 @@ impl<'a, S: Source<Volatility = NonVolatile>, C: Config> Parser<'a, S, C> {
     {
         let mut char = self.skip_whitespace();
 
+        #[cfg(feature = "alloc")]
+        let cur = self.idx();
+        #[cfg(feature = "alloc")]
+        if let Some(index) = self.src.index()
+            && let Some(idx) = index.locate(cur)
+        {
+            let (off, ..) = index.walk::<Error, _>(cur, idx, p);
+
+            self.inc(off - cur);
+            return self.cur();
+        }
+
         'main: for pointer in p {
             if let Some(key) = pointer.as_key() {
                 loop {
@@ -336,7 +405,7 @@ impl<'a, S: Source<Volatility = NonVolatile>, C: Config> Parser<'a, S, C> {
         char
     }
 
-    unsafe fn string_match_unchecked(&mut self, target: &str) -> bool {
+    pub(crate) unsafe fn string_match_unchecked(&mut self, target: &str) -> bool {
         let (mut start, end) = if target.is_empty() {
             (&raw const INVALID[1], &raw const INVALID[1])
         } else {
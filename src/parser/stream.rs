@@ -0,0 +1,245 @@
+use crate::{
+    Parser,
+    config::Config,
+    source::{Source, Volatility},
+    value::builder::{ErrorBuilder, ValueBuilder},
+};
+use core::marker::PhantomData;
+
+/// Iterator over successive top-level JSON values.
+///
+/// Created by [`Parser::iter`]. Values may be separated by any amount of whitespace,
+/// which makes it suitable for newline delimited JSON (NDJSON / JSON Lines). Consumed
+/// data is trimmed from volatile sources as it goes.
+///
+/// The iterator stops after the first error.
+pub struct Stream<'p, 'a, S: Source + 'a, C: Config, V> {
+    parser: &'p mut Parser<'a, S, C>,
+    offset: usize,
+    done: bool,
+    __: PhantomData<V>,
+}
+
+impl<'p, 'a, S: Source, C: Config, V: ValueBuilder<'a, S>> Stream<'p, 'a, S, C, V> {
+    #[inline]
+    pub(super) fn new(parser: &'p mut Parser<'a, S, C>) -> Self {
+        const {
+            assert!(
+                !(V::LAZY & S::Volatility::IS_VOLATILE),
+                "source must be non volatile if the value builder is lazy"
+            )
+        }
+
+        Self {
+            parser,
+            offset: 0,
+            done: false,
+            __: PhantomData,
+        }
+    }
+
+    /// Returns the starting byte offset of the most recently yielded value.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{Parser, Value};
+    ///
+    /// let mut parser = Parser::from_str("1\n[2]\n");
+    /// let mut iter = parser.iter::<Value>();
+    ///
+    /// iter.next();
+    /// iter.next();
+    /// assert_eq!(iter.offset(), 2);
+    /// ```
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, S: Source, C: Config, V: ValueBuilder<'a, S>> Iterator for Stream<'_, 'a, S, C, V> {
+    type Item = Result<V, V::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let parser = &mut *self.parser;
+        let char = parser.skip_whitespace();
+        if char == 0 {
+            self.done = true;
+            return parser.take_io_error().map(Err);
+        }
+
+        self.offset = parser.idx();
+        parser.allocated = 0;
+        parser.dec();
+
+        Some(parser.stream_value().map_err(|e| {
+            self.done = true;
+            parser.fail(e)
+        }))
+    }
+}
+
+/// Iterator over the elements of a JSON array.
+///
+/// Created by [`Parser::iter_array`]. Each element is parsed only when requested, so
+/// the array is never materialized as a whole. Consumed data is trimmed from volatile
+/// sources as it goes, keeping the memory usage bounded by the largest element.
+///
+/// Any data following the array is left untouched. The iterator stops after the first error.
+pub struct ArrayStream<'p, 'a, S: Source + 'a, C: Config, V> {
+    parser: &'p mut Parser<'a, S, C>,
+    offset: usize,
+    started: bool,
+    done: bool,
+    __: PhantomData<V>,
+}
+
+impl<'p, 'a, S: Source, C: Config, V: ValueBuilder<'a, S>> ArrayStream<'p, 'a, S, C, V> {
+    #[inline]
+    pub(super) fn new(parser: &'p mut Parser<'a, S, C>) -> Self {
+        const {
+            assert!(
+                !(V::LAZY & S::Volatility::IS_VOLATILE),
+                "source must be non volatile if the value builder is lazy"
+            )
+        }
+
+        Self {
+            parser,
+            offset: 0,
+            started: false,
+            done: false,
+            __: PhantomData,
+        }
+    }
+
+    /// Returns the starting byte offset of the most recently yielded element.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, S: Source, C: Config, V: ValueBuilder<'a, S>> Iterator for ArrayStream<'_, 'a, S, C, V> {
+    type Item = Result<V, V::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let parser = &mut *self.parser;
+        match parser.next_element(self.started) {
+            Ok(true) => self.started = true,
+            Ok(false) => {
+                self.done = true;
+                return None;
+            }
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+
+        self.offset = parser.idx().wrapping_add(1);
+        parser.allocated = 0;
+        Some(parser.stream_value().map_err(|e| {
+            self.done = true;
+            parser.fail(e)
+        }))
+    }
+}
+
+impl<'a, S: Source, C: Config> Parser<'a, S, C> {
+    /// Moves right before the next element of the array being streamed.
+    ///
+    /// Returns `false` once the array ends. `started` tells whether the opening
+    /// bracket has been consumed already.
+    #[allow(unused_mut)]
+    pub(crate) fn next_element<E: ErrorBuilder>(&mut self, started: bool) -> Result<bool, E> {
+        let mut tmp = self.skip_whitespace();
+        let mut err = 'err: {
+            if !started {
+                if tmp != b'[' {
+                    break 'err match tmp {
+                        0 => E::eof(),
+                        _ => E::unexpected_token(),
+                    };
+                }
+
+                if self.enter() {
+                    return Err(self.depth_error());
+                }
+
+                tmp = self.skip_whitespace();
+                if tmp == b']' {
+                    self.leave();
+                    return Ok(false);
+                }
+            } else {
+                let comma = tmp == b',';
+                if comma {
+                    tmp = self.skip_whitespace();
+                }
+
+                if tmp == b']' {
+                    if !comma || self.cfg.trailing_comma() {
+                        self.leave();
+                        return Ok(false);
+                    }
+
+                    #[cfg(feature = "span")]
+                    self.dec();
+                    break 'err E::trailing_comma();
+                }
+
+                if !comma && !self.cfg.comma() {
+                    break 'err match tmp {
+                        0 => E::eof(),
+                        _ => E::unexpected_token(),
+                    };
+                }
+            }
+
+            if tmp == 0 {
+                break 'err E::eof();
+            }
+
+            self.dec();
+            if S::Volatility::IS_VOLATILE {
+                let tmp = self.idx().wrapping_add(1);
+                self.src.trim(tmp);
+            }
+
+            return Ok(true);
+        };
+
+        #[cfg(feature = "span")]
+        err.apply_span(self.idx(), self.idx());
+        Err(self.fail(err))
+    }
+
+    // parses the upcoming value.
+    fn stream_value<V: ValueBuilder<'a, S>>(&mut self) -> Result<V, V::Error> {
+        if !V::LAZY {
+            return self.value();
+        }
+
+        // source is non volatile
+        let char = self.skip_whitespace();
+        let start = self.idx();
+        match char {
+            b'"' => self.skip_string(),
+            b'{' => self.skip_object(),
+            b'[' => self.skip_array(),
+            _ => unsafe { self.skip_literal() },
+        }?;
+
+        let len = self.idx() + 1 - start;
+        unsafe { self.lazy(start, len) }
+    }
+}
@@ -4,38 +4,57 @@ use crate::{
     JsonPointer, Parser,
     config::Config,
     misc::{ESC_LUT, NUM_LUT, unlikely},
+    parser::Number,
     serde::unchecked::Unchecked,
     source::{NonVolatile, Source, Volatility},
 };
 use core::{
-    fmt::{self, Display, Formatter},
+    fmt::{self, Debug, Display, Formatter},
+    marker::PhantomData,
+    mem::discriminant,
     slice::from_raw_parts,
     str::from_utf8_unchecked,
 };
 use serde_core::{
     Deserialize, Deserializer,
-    de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Unexpected, Visitor},
+    de::{
+        self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Unexpected, Visitor,
+        value::{BorrowedStrDeserializer, StrDeserializer},
+    },
     forward_to_deserialize_any,
 };
 use simdutf8::basic::from_utf8;
 
 #[cfg(feature = "alloc")]
 use {
-    crate::{misc::capacity_overflow, source::NullPadded},
+    crate::{
+        config::DuplicateKeys, misc::capacity_overflow, source::NullPadded,
+        value::owned::String as OwnedString,
+    },
     alloc::{
         alloc::{alloc, dealloc, handle_alloc_error, realloc},
         boxed::Box,
         string::{String, ToString},
+        vec::Vec,
     },
     core::{alloc::Layout, ptr::dangling_mut},
 };
 
 #[cfg(feature = "std")]
-use std::io::Read;
+use {
+    crate::{config::CTConfig, source::Reader},
+    std::io::Read,
+};
 
 #[cfg(feature = "span")]
 use super::span::*;
 
+// keys of an object, to look up duplicates in.
+#[cfg(feature = "std")]
+type KeySet = std::collections::HashSet<OwnedString>;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+type KeySet = alloc::collections::BTreeSet<OwnedString>;
+
 pub type Result<T> = core::result::Result<T, Error>;
 
 impl<S: Source, C: Config> Parser<'_, S, C> {
@@ -68,6 +87,20 @@ impl<S: Source, C: Config> Parser<'_, S, C> {
         #[cfg(feature = "span")]
         let stamp = self.idx();
 
+        if self.is_number(tmp) {
+            return match self.number() {
+                Ok(Number::Int(val, false)) => visitor.visit_u64(val),
+                Ok(Number::Int(val, _)) => visitor.visit_i64(val as _),
+                Ok(Number::Float(val)) => visitor.visit_f64(val),
+                Err(kind) => {
+                    let mut tmp = self.err(kind);
+                    #[cfg(feature = "span")]
+                    (tmp.span[0] = stamp);
+                    Err(tmp)
+                }
+            };
+        }
+
         if NUM_LUT[tmp as usize] {
             let neg = tmp == b'-';
             if neg {
@@ -161,16 +194,65 @@ impl<S: Source, C: Config> Parser<'_, S, C> {
     }
 
     #[cold]
-    fn err(&mut self, kind: Kind) -> Error {
-        Error {
-            #[cfg(not(feature = "alloc"))]
-            kind,
-            #[cfg(feature = "alloc")]
-            kind: Box::new(kind),
-            #[cfg(feature = "span")]
-            span: [self.idx(); 2],
+    fn close_string_unless_limit(&mut self, quote: u8, kind: Kind) -> Kind {
+        match kind {
+            // no point in going through the rest of it
+            Kind::StringTooLong | Kind::AllocLimitExceeded => kind,
+            _ => self.close_string(quote, kind),
         }
     }
+
+    #[cold]
+    fn err(&mut self, kind: Kind) -> Error {
+        // the source acts as if it reached EOF on failure, so whatever we
+        // ended up with is most likely caused by it.
+        #[cfg(feature = "std")]
+        let kind = match self.src.take_error() {
+            Some(v) => Kind::Io(v),
+            _ => kind,
+        };
+
+        #[allow(unused_mut)]
+        let mut err: Error = kind.into();
+
+        #[cfg(feature = "span")]
+        (err.span = [self.idx(); 2]);
+        err
+    }
+}
+
+impl<'de, S: Source, C: Config> Parser<'de, S, C> {
+    // unquoted keys can not have escape sequences, so they are taken as is from the source.
+    // the parser must be at the first byte of the key. the returned string only lives as long
+    // as the source is not trimmed, which is right away for volatile sources.
+    unsafe fn ident_str(&mut self) -> Result<&'de str> {
+        let start = self.idx();
+        let kind = 'err: {
+            if let Err(e) = self.skip_ident() {
+                break 'err e;
+            }
+
+            let len = self.idx() + 1 - start;
+            if unlikely(len > self.cfg.max_string_len()) {
+                break 'err Kind::StringTooLong;
+            }
+
+            if self.allocate(len) {
+                break 'err Kind::AllocLimitExceeded;
+            }
+
+            return Ok(from_utf8_unchecked(from_raw_parts(
+                self.src.ptr(start),
+                len,
+            )));
+        };
+        #[allow(unused_mut)]
+        let mut err = self.err(kind);
+
+        #[cfg(feature = "span")]
+        (err.span[0] = start);
+        Err(err)
+    }
 }
 
 macro_rules! deserialize_literal {
@@ -191,7 +273,7 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
         self.dec();
 
         match tmp {
-            b'"' => self.deserialize_str(visitor),
+            v if self.is_quote(v) => self.deserialize_str(visitor),
             b'{' => self.deserialize_map(visitor),
             b'[' if self.idx().wrapping_add(2) < self.src.len() => self.deserialize_seq(visitor),
             0 => Err(self.err(Kind::Eof)),
@@ -205,7 +287,8 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
 
     #[allow(unused_mut)]
     fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-        if self.skip_whitespace() != b'"' {
+        let quote = self.skip_whitespace();
+        if !self.is_quote(quote) {
             return Err(self.err(Kind::UnexpectedToken));
         }
 
@@ -216,11 +299,17 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
 
         match S::INSITU {
             true => unsafe {
+                let stamp = self.idx();
                 let start = self.cur_ptr_mut().add(1);
                 let mut offset = start;
                 let mut len = 0;
                 let err = loop {
-                    if self.simd_str() {
+                    if self.string_too_long(stamp) {
+                        break Kind::StringTooLong;
+                    }
+
+                    // the simd routines only look for '"'
+                    if quote == b'"' && self.simd_str() {
                         continue;
                     }
 
@@ -230,15 +319,24 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                     }
 
                     break match self.cur() {
-                        b'"' => {
+                        v if v == quote => {
+                            // the limit is only checked per chunk above
+                            if self.string_too_long(stamp + 1) {
+                                break Kind::StringTooLong;
+                            }
+
                             let count = self.cur_ptr().offset_from_unsigned(offset);
-                            if len != 0 {
+                            if offset != start {
                                 start.add(len).copy_from(offset, count);
                             }
 
                             len += count;
                             let tmp = from_raw_parts(start, len);
 
+                            if self.allocate(len) {
+                                break Kind::AllocLimitExceeded;
+                            }
+
                             return if S::UTF8 || from_utf8(tmp).is_ok() {
                                 visitor.visit_borrowed_str(from_utf8_unchecked(tmp))
                             } else {
@@ -268,9 +366,11 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                                 continue;
                             }
 
-                            if tmp == b'u'
-                                && let Some(v) = self.unicode_escape(&mut [0; 4])
-                            {
+                            let mut esc = [0; 4];
+                            if let Some(v) = match tmp {
+                                b'u' => self.unicode_escape(&mut esc),
+                                _ => self.dialect_escape(&mut esc),
+                            } {
                                 ptr.copy_from_nonoverlapping(v.as_ptr(), v.len());
                                 offset = self.cur_ptr_mut().add(1);
                                 len += v.len();
@@ -288,7 +388,7 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                         _ => Kind::ControlCharacter,
                     };
                 };
-                let err = self.close_string(err);
+                let err = self.close_string_unless_limit(quote, err);
                 let mut err = self.err(err);
 
                 #[cfg(feature = "span")]
@@ -301,6 +401,7 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
             },
             #[cfg(feature = "alloc")]
             _ if S::Volatility::IS_VOLATILE => unsafe {
+                let stamp = self.idx();
                 #[cfg(feature = "span")]
                 let start = self.idx();
                 let mut offset = self.idx() + 1;
@@ -310,7 +411,12 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
 
                 'main: {
                     let err = loop {
-                        if self.simd_str() {
+                        if self.string_too_long(stamp) {
+                            break Kind::StringTooLong;
+                        }
+
+                        // the simd routines only look for '"'
+                        if quote == b'"' && self.simd_str() {
                             continue;
                         }
 
@@ -320,7 +426,11 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                         }
 
                         break match self.cur() {
-                            b'"' => break 'main,
+                            // the limit is only checked per chunk above
+                            v if v == quote => match self.string_too_long(stamp + 1) {
+                                true => Kind::StringTooLong,
+                                _ => break 'main,
+                            },
                             b'\\' => {
                                 let count = self.idx() - offset;
                                 // `len + count` < isize::MAX, 4 << usize::MAX - isize::MAX
@@ -368,9 +478,11 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                                     continue;
                                 }
 
-                                if tmp == b'u'
-                                    && let Some(v) = self.unicode_escape(&mut [0; 4])
-                                {
+                                let mut esc = [0; 4];
+                                if let Some(v) = match tmp {
+                                    b'u' => self.unicode_escape(&mut esc),
+                                    _ => self.dialect_escape(&mut esc),
+                                } {
                                     buf.copy_from_nonoverlapping(v.as_ptr(), v.len());
                                     offset = self.idx() + 1;
                                     len += v.len();
@@ -383,7 +495,7 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                             _ => Kind::ControlCharacter,
                         };
                     };
-                    let err = self.close_string(err);
+                    let err = self.close_string_unless_limit(quote, err);
                     let mut err = self.err(err);
 
                     #[cfg(feature = "span")]
@@ -420,21 +532,31 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                 buf.add(len)
                     .copy_from_nonoverlapping(self.src.ptr(offset), count);
 
-                if S::UTF8 || from_utf8(from_raw_parts(buf, new_len)).is_ok() {
-                    return visitor.visit_string(String::from_raw_parts(buf, new_len, cap));
-                }
+                let kind = 'err: {
+                    if S::UTF8 || from_utf8(from_raw_parts(buf, new_len)).is_ok() {
+                        let tmp = String::from_raw_parts(buf, new_len, cap);
+                        if self.allocate(new_len) {
+                            break 'err Kind::AllocLimitExceeded;
+                        }
 
-                if cap != 0 {
-                    dealloc(buf, Layout::array::<u8>(cap).unwrap_unchecked())
-                }
+                        return visitor.visit_string(tmp);
+                    }
+
+                    if cap != 0 {
+                        dealloc(buf, Layout::array::<u8>(cap).unwrap_unchecked())
+                    }
+
+                    Kind::UnexpectedToken
+                };
 
-                let mut tmp = self.err(Kind::UnexpectedToken);
+                let mut tmp = self.err(kind);
                 #[cfg(feature = "span")]
                 (tmp.span[0] = start);
                 Err(tmp)
             },
             #[cfg(feature = "alloc")]
             _ => unsafe {
+                let stamp = self.idx();
                 #[cfg(feature = "span")]
                 let start = self.idx();
                 let mut offset = self.cur_ptr().add(1);
@@ -444,7 +566,12 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
 
                 'main: {
                     let err = loop {
-                        if self.simd_str() {
+                        if self.string_too_long(stamp) {
+                            break Kind::StringTooLong;
+                        }
+
+                        // the simd routines only look for '"'
+                        if quote == b'"' && self.simd_str() {
                             continue;
                         }
 
@@ -454,7 +581,11 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                         }
 
                         break match self.cur() {
-                            b'"' => break 'main,
+                            // the limit is only checked per chunk above
+                            v if v == quote => match self.string_too_long(stamp + 1) {
+                                true => Kind::StringTooLong,
+                                _ => break 'main,
+                            },
                             b'\\' => {
                                 let count = self.cur_ptr().offset_from_unsigned(offset);
                                 // `len + count` < isize::MAX, 4 << usize::MAX - isize::MAX
@@ -501,9 +632,11 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                                     continue;
                                 }
 
-                                if tmp == b'u'
-                                    && let Some(v) = self.unicode_escape(&mut [0; 4])
-                                {
+                                let mut esc = [0; 4];
+                                if let Some(v) = match tmp {
+                                    b'u' => self.unicode_escape(&mut esc),
+                                    _ => self.dialect_escape(&mut esc),
+                                } {
                                     buf.copy_from_nonoverlapping(v.as_ptr(), v.len());
                                     offset = self.cur_ptr().add(1);
                                     len += v.len();
@@ -521,7 +654,7 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                             _ => Kind::ControlCharacter,
                         };
                     };
-                    let err = self.close_string(err);
+                    let err = self.close_string_unless_limit(quote, err);
                     let mut err = self.err(err);
 
                     #[cfg(feature = "span")]
@@ -534,8 +667,12 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                     return Err(err);
                 }
 
-                if len == 0 {
+                if cap == 0 {
                     let tmp = from_raw_parts(offset, self.cur_ptr().offset_from_unsigned(offset));
+                    if self.allocate(tmp.len()) {
+                        return Err(self.err(Kind::AllocLimitExceeded));
+                    }
+
                     return if S::UTF8 || from_utf8(tmp).is_ok() {
                         visitor.visit_borrowed_str(from_utf8_unchecked(tmp))
                     } else {
@@ -566,7 +703,12 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
                 buf.add(len).copy_from_nonoverlapping(offset, count);
 
                 if S::UTF8 || from_utf8(from_raw_parts(buf, new_len)).is_ok() {
-                    return visitor.visit_string(String::from_raw_parts(buf, new_len, cap));
+                    let tmp = String::from_raw_parts(buf, new_len, cap);
+                    if self.allocate(new_len) {
+                        return Err(self.err(Kind::AllocLimitExceeded));
+                    }
+
+                    return visitor.visit_string(tmp);
                 }
 
                 if cap != 0 {
@@ -634,11 +776,15 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
         }
 
         let tmp = match self.skip_whitespace() {
+            b'[' if self.enter() => Kind::DepthLimitExceeded,
             b'[' => {
                 let tmp = visitor.visit_seq(CommaSeparated::new(self))?;
 
                 match self.skip_whitespace_alt() {
-                    b']' => return Ok(tmp),
+                    b']' => {
+                        self.leave();
+                        return Ok(tmp);
+                    }
                     0 => Kind::Eof,
                     _ => Kind::UnexpectedToken,
                 }
@@ -669,7 +815,12 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
             self.src.trim(tmp);
         }
         let tmp = match self.skip_whitespace() {
-            b'{' => return visitor.visit_map(CommaSeparated::new(self)),
+            b'{' if self.enter() => Kind::DepthLimitExceeded,
+            b'{' => {
+                let tmp = visitor.visit_map(CommaSeparated::new(self));
+                self.leave();
+                return tmp;
+            }
             0 => Kind::Eof,
             _ => Kind::UnexpectedToken,
         };
@@ -684,12 +835,21 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
         visitor: V,
     ) -> Result<V::Value> {
         let tmp = match self.skip_whitespace() {
-            b'{' => return visitor.visit_map(CommaSeparated::new(self)),
+            b'{' if self.enter() => Kind::DepthLimitExceeded,
+            b'{' => {
+                let tmp = visitor.visit_map(CommaSeparated::new(self));
+                self.leave();
+                return tmp;
+            }
+            b'[' if self.enter() => Kind::DepthLimitExceeded,
             b'[' => {
                 let tmp = visitor.visit_seq(CommaSeparated::new(self))?;
 
                 match self.skip_whitespace_alt() {
-                    b']' => return Ok(tmp),
+                    b']' => {
+                        self.leave();
+                        return Ok(tmp);
+                    }
                     0 => Kind::Eof,
                     _ => Kind::UnexpectedToken,
                 }
@@ -708,16 +868,20 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
         visitor: V,
     ) -> Result<V::Value> {
         let tmp = match self.skip_whitespace() {
+            b'{' if self.enter() => Kind::DepthLimitExceeded,
             b'{' => {
                 let tmp = visitor.visit_enum(VariantAccess(self))?;
 
                 match self.skip_whitespace() {
-                    b'}' => return Ok(tmp),
+                    b'}' => {
+                        self.leave();
+                        return Ok(tmp);
+                    }
                     0 => Kind::Eof,
                     _ => Kind::UnexpectedToken,
                 }
             }
-            b'"' => return visitor.visit_enum(UnitVariantAccess(self)),
+            v if self.is_quote(v) => return visitor.visit_enum(UnitVariantAccess(self)),
             0 => Kind::Eof,
             _ => Kind::UnexpectedToken,
         };
@@ -726,7 +890,17 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
     }
 
     fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-        self.deserialize_str(visitor)
+        let tmp = self.skip_whitespace();
+        if !self.is_ident(tmp) {
+            self.dec();
+            return self.deserialize_str(visitor);
+        }
+
+        let tmp = unsafe { self.ident_str()? };
+        match S::Volatility::IS_VOLATILE {
+            true => visitor.visit_str(tmp),
+            _ => visitor.visit_borrowed_str(tmp),
+        }
     }
 
     fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
@@ -761,12 +935,106 @@ impl<'de, S: Source, C: Config> Deserializer<'de> for &mut Parser<'de, S, C> {
 struct CommaSeparated<'a, 'de, S: Source, C: Config> {
     de: &'a mut Parser<'de, S, C>,
     flag: bool,
+    len: usize,
+    // keys seen so far in the object.
+    #[cfg(feature = "alloc")]
+    keys: KeySet,
+    // whether the entry is followed by another one with the same key.
+    #[cfg(feature = "alloc")]
+    superseded: Vec<bool>,
+    // key of the current entry, kept for the error path.
+    #[cfg(feature = "alloc")]
+    key: Option<OwnedString>,
 }
 
 impl<'a, 'de, S: Source, C: Config> CommaSeparated<'a, 'de, S, C> {
     #[inline(always)]
     fn new(de: &'a mut Parser<'de, S, C>) -> Self {
-        CommaSeparated { de, flag: true }
+        CommaSeparated {
+            de,
+            flag: true,
+            len: 0,
+            #[cfg(feature = "alloc")]
+            keys: KeySet::new(),
+            #[cfg(feature = "alloc")]
+            superseded: Vec::new(),
+            #[cfg(feature = "alloc")]
+            key: None,
+        }
+    }
+
+    // skips the upcoming entry if it should be ignored under the duplicate key policy.
+    // the parser must be at the opening quote of the key.
+    #[cfg(feature = "alloc")]
+    fn skip_duplicate(&mut self) -> Result<bool> {
+        if self.de.cfg.duplicate_keys() == DuplicateKeys::LastWins {
+            if self.len == 1 {
+                self.scan();
+            }
+
+            if !self.superseded.get(self.len - 1).copied().unwrap_or(false) {
+                return Ok(false);
+            }
+
+            self.de.skip_key()?;
+        } else {
+            #[cfg(feature = "span")]
+            let start = self.de.idx();
+            let checkpoint = self.de.checkpoint();
+            let key = unsafe { self.de.key::<OwnedString, OwnedString, Error>()? };
+
+            if self.keys.insert(key) {
+                self.de.restore(checkpoint);
+                return Ok(false);
+            }
+
+            if self.de.cfg.duplicate_keys() == DuplicateKeys::Reject {
+                #[allow(unused_mut)]
+                let mut err = self.de.err(Kind::DuplicateKey);
+
+                #[cfg(feature = "span")]
+                (err.span[0] = start);
+                return Err(err);
+            }
+        }
+
+        if self.de.skip_whitespace() != b':' {
+            return Err(self.de.err(Kind::ExpectedColon));
+        }
+
+        self.de.skip_value().map(|_| true)
+    }
+
+    // looks ahead for keys that appear again later in the object.
+    // invalid JSON is left for the actual deserialization to report.
+    #[cfg(feature = "alloc")]
+    fn scan(&mut self) {
+        let checkpoint = self.de.checkpoint();
+        let mut keys = Vec::new();
+
+        self.de.dec();
+        loop {
+            match self.de.skip_whitespace() {
+                b',' => continue,
+                v if self.de.is_key(v) => {}
+                _ => break,
+            }
+
+            match unsafe { self.de.key::<OwnedString, OwnedString, Error>() } {
+                Ok(v) => keys.push(v),
+                _ => break,
+            }
+
+            if self.de.skip_whitespace() != b':' || self.de.skip_value::<Error>().is_err() {
+                break;
+            }
+        }
+
+        // an entry is superseded if its key is seen again later on
+        let mut seen = KeySet::new();
+        self.superseded = keys.into_iter().rev().map(|v| !seen.insert(v)).collect();
+        self.superseded.reverse();
+        self.de.restore(checkpoint);
     }
 }
 
@@ -779,10 +1047,41 @@ impl<'a, 'de, S: Source, C: Config> MapAccess<'de> for CommaSeparated<'a, 'de, S
         loop {
             let tmp = self.de.skip_whitespace();
             let err = match tmp {
-                b'"' if self.flag => {
-                    self.de.dec();
-                    self.flag = false;
-                    return seed.deserialize(&mut *self.de).map(Some);
+                v if self.flag && self.de.is_key(v) => {
+                    self.len += 1;
+                    if unlikely(self.len > self.de.cfg.max_container_len()) {
+                        Kind::ContainerTooLarge
+                    } else {
+                        self.flag = false;
+
+                        #[cfg(feature = "alloc")]
+                        if self.de.cfg.duplicate_keys() != DuplicateKeys::Allow
+                            && self.skip_duplicate()?
+                        {
+                            wtf = true;
+                            continue;
+                        }
+
+                        #[cfg(feature = "alloc")]
+                        if self.de.cfg.error_path() {
+                            let checkpoint = self.de.checkpoint();
+                            self.key =
+                                Some(unsafe { self.de.key::<OwnedString, OwnedString, Error>()? });
+                            self.de.restore(checkpoint);
+                        }
+
+                        if !self.de.is_quote(tmp) {
+                            let key = unsafe { self.de.ident_str()? };
+                            return match S::Volatility::IS_VOLATILE {
+                                true => seed.deserialize(StrDeserializer::new(key)),
+                                _ => seed.deserialize(BorrowedStrDeserializer::new(key)),
+                            }
+                            .map(Some);
+                        }
+
+                        self.de.dec();
+                        return seed.deserialize(&mut *self.de).map(Some);
+                    }
                 }
                 b',' => {
                     if !self.flag {
@@ -819,7 +1118,13 @@ impl<'a, 'de, S: Source, C: Config> MapAccess<'de> for CommaSeparated<'a, 'de, S
             return Err(self.de.err(Kind::ExpectedColon));
         }
 
-        seed.deserialize(&mut *self.de)
+        match seed.deserialize(&mut *self.de) {
+            #[cfg(feature = "alloc")]
+            Err(err) if self.de.cfg.error_path() => {
+                Err(err.prepend_key(self.key.as_deref().unwrap_or_default()))
+            }
+            v => v,
+        }
     }
 }
 
@@ -843,9 +1148,21 @@ impl<'a, 'de, S: Source, C: Config> SeqAccess<'de> for CommaSeparated<'a, 'de, S
                     }
                 },
                 _ if self.flag => {
-                    self.de.dec();
-                    self.flag = false;
-                    return seed.deserialize(&mut *self.de).map(Some);
+                    self.len += 1;
+                    if unlikely(self.len > self.de.cfg.max_container_len()) {
+                        Kind::ContainerTooLarge
+                    } else {
+                        self.de.dec();
+                        self.flag = false;
+
+                        return match seed.deserialize(&mut *self.de) {
+                            #[cfg(feature = "alloc")]
+                            Err(err) if self.de.cfg.error_path() => {
+                                Err(err.prepend_index(self.len - 1))
+                            }
+                            v => v.map(Some),
+                        };
+                    }
                 }
                 b',' if !self.flag => {
                     self.flag = true;
@@ -949,18 +1266,29 @@ impl<'a, 'de, S: Source, C: Config> de::VariantAccess<'de> for UnitVariantAccess
 }
 
 /// Represents error occurred while parsing.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(PartialEq, Eq)]
 pub struct Error {
     #[cfg(feature = "alloc")]
-    pub(super) kind: Box<Kind>,
+    repr: Box<Repr>,
     #[cfg(not(feature = "alloc"))]
-    pub(super) kind: Kind,
+    kind: Kind,
     #[cfg(feature = "span")]
     pub(crate) span: [usize; 2],
 }
 
+// kept behind a single pointer so that the error stays small.
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq)]
+struct Repr {
+    kind: Kind,
+    // JSON pointer of where the error occurred, if tracked.
+    path: String,
+    // byte offset of where parsing stopped, if known.
+    offset: Option<usize>,
+}
+
 /// Represents the type of error.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug)]
 pub enum Kind {
     /// Serde specific error.
     #[cfg(feature = "alloc")]
@@ -991,13 +1319,114 @@ pub enum Kind {
     LeadingZero,
     /// Number is bigger than it can represent.
     NumberOverflow,
+    /// Nesting depth of arrays and objects exceeded the configured limit.
+    DepthLimitExceeded,
+    /// String length exceeded the configured limit.
+    StringTooLong,
+    /// Number of elements in an array or entries in an object exceeded the configured limit.
+    ContainerTooLarge,
+    /// Allocated bytes exceeded the configured limit.
+    AllocLimitExceeded,
+    /// Object contains the same key more than once.
+    DuplicateKey,
+    /// I/O error while reading the source.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
 }
 
+impl PartialEq for Kind {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            #[cfg(feature = "alloc")]
+            (Kind::Message(a), Kind::Message(b)) => a == b,
+            #[cfg(feature = "std")]
+            (Kind::Io(a), Kind::Io(b)) => a.kind() == b.kind(),
+            _ => discriminant(self) == discriminant(other),
+        }
+    }
+}
+
+impl Eq for Kind {}
+
 impl Error {
     /// Returns the error kind.
     #[inline]
     pub fn kind(&self) -> &Kind {
-        &self.kind
+        #[cfg(feature = "alloc")]
+        let kind = &self.repr.kind;
+        #[cfg(not(feature = "alloc"))]
+        let kind = &self.kind;
+
+        kind
+    }
+
+    /// Returns the JSON pointer path of where the error occurred, e.g. `/orders/3/price`.
+    ///
+    /// Only tracked when enabled with [`RTConfig::error_path`](crate::config::RTConfig::error_path).
+    /// Returns `None` if it wasn't tracked or the error occurred at the root.
+    #[inline]
+    #[cfg(feature = "alloc")]
+    pub fn path(&self) -> Option<&str> {
+        match self.repr.path.is_empty() {
+            true => None,
+            _ => Some(&self.repr.path),
+        }
+    }
+
+    // prepends the given object key to the path.
+    #[cold]
+    #[cfg(feature = "alloc")]
+    pub(super) fn prepend_key(mut self, key: &str) -> Self {
+        let path = &mut self.repr.path;
+        let mut tmp = String::with_capacity(key.len() + 1 + path.len());
+
+        tmp.push('/');
+        for c in key.chars() {
+            match c {
+                '~' => tmp.push_str("~0"),
+                '/' => tmp.push_str("~1"),
+                _ => tmp.push(c),
+            }
+        }
+
+        tmp.push_str(path);
+        *path = tmp;
+        self
+    }
+
+    // prepends the given array index to the path.
+    #[cold]
+    #[cfg(feature = "alloc")]
+    pub(super) fn prepend_index(mut self, idx: usize) -> Self {
+        let mut buf = itoa::Buffer::new();
+        let idx = buf.format(idx);
+        let path = &mut self.repr.path;
+
+        path.insert_str(0, idx);
+        path.insert(0, '/');
+        self
+    }
+
+    /// Returns the byte offset of where parsing stopped.
+    ///
+    /// Recorded when the error comes out of the parser, e.g. through the `from_*` functions
+    /// or the iterators in this module. Returns `None` for errors that did not, such as the
+    /// ones of [`from_value`](crate::serde::from_value), or without the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// let err = flexon::from_str::<Vec<u8>>("[1, 2 3]").unwrap_err();
+    /// # #[cfg(feature = "alloc")]
+    /// assert_eq!(err.offset(), Some(6));
+    /// ```
+    #[inline]
+    pub fn offset(&self) -> Option<usize> {
+        #[cfg(feature = "alloc")]
+        let offset = self.repr.offset;
+        #[cfg(not(feature = "alloc"))]
+        let offset = None;
+
+        offset
     }
 
     /// Returns the starting byte offset of the error.
@@ -1015,16 +1444,13 @@ impl Error {
     }
 }
 
-impl Display for Error {
+impl Display for Kind {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        #[cfg(feature = "alloc")]
-        let kind = &*self.kind;
-        #[cfg(not(feature = "alloc"))]
-        let kind = &self.kind;
-
-        f.write_str(match kind {
+        f.write_str(match self {
             #[cfg(feature = "alloc")]
             Kind::Message(data) => data,
+            #[cfg(feature = "std")]
+            Kind::Io(err) => return Display::fmt(err, f),
             #[cfg(not(feature = "alloc"))]
             Kind::Unknown => "unknown serde error",
             Kind::Eof => "eof while parsing",
@@ -1039,24 +1465,68 @@ impl Display for Error {
             Kind::TrailingDecimal => "trailing decimal in number",
             Kind::LeadingZero => "leading zero in number",
             Kind::NumberOverflow => "number too large",
+            Kind::DepthLimitExceeded => "nesting depth limit exceeded",
+            Kind::StringTooLong => "string too long",
+            Kind::ContainerTooLarge => "too many elements",
+            Kind::AllocLimitExceeded => "allocation limit exceeded",
+            Kind::DuplicateKey => "duplicate key",
         })
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.kind(), f)?;
+
+        #[cfg(feature = "alloc")]
+        if let Some(path) = self.path() {
+            write!(f, " at {path}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut tmp = f.debug_struct("Error");
+        tmp.field("kind", self.kind());
+
+        #[cfg(feature = "alloc")]
+        if let Some(path) = self.path() {
+            tmp.field("path", &path);
+        }
+
+        if let Some(offset) = self.offset() {
+            tmp.field("offset", &offset);
+        }
+
+        #[cfg(feature = "span")]
+        tmp.field("span", &self.span);
+        tmp.finish()
+    }
+}
+
 impl de::Error for Error {
     fn custom<T: Display>(_msg: T) -> Self {
-        Error {
-            #[cfg(feature = "alloc")]
-            kind: Box::new(Kind::Message(_msg.to_string().into_boxed_str())),
-            #[cfg(not(feature = "alloc"))]
-            kind: Kind::Unknown,
-            #[cfg(feature = "span")]
-            span: [0; 2],
-        }
+        #[cfg(feature = "alloc")]
+        let kind = Kind::Message(_msg.to_string().into_boxed_str());
+        #[cfg(not(feature = "alloc"))]
+        let kind = Kind::Unknown;
+
+        kind.into()
     }
 }
 
-impl core::error::Error for Error {}
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self.kind() {
+            #[cfg(feature = "std")]
+            Kind::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 #[allow(non_local_definitions)]
 const _: fn() = || {
@@ -1129,6 +1599,37 @@ const _: fn() = || {
             Kind::UnexpectedToken
         }
 
+        #[inline]
+        fn depth_limit_exceeded() -> Self {
+            Kind::DepthLimitExceeded
+        }
+
+        #[inline]
+        fn string_too_long() -> Self {
+            Kind::StringTooLong
+        }
+
+        #[inline]
+        fn container_too_large() -> Self {
+            Kind::ContainerTooLarge
+        }
+
+        #[inline]
+        fn alloc_limit_exceeded() -> Self {
+            Kind::AllocLimitExceeded
+        }
+
+        #[inline]
+        fn duplicate_key() -> Self {
+            Kind::DuplicateKey
+        }
+
+        #[inline]
+        #[cfg(feature = "std")]
+        fn io(err: std::io::Error) -> Self {
+            Kind::Io(err)
+        }
+
         fn apply_span(&mut self, _: usize, _: usize) {}
     }
 
@@ -1199,8 +1700,48 @@ const _: fn() = || {
             Kind::UnexpectedToken.into()
         }
 
+        #[inline]
+        fn depth_limit_exceeded() -> Self {
+            Kind::DepthLimitExceeded.into()
+        }
+
+        #[inline]
+        fn string_too_long() -> Self {
+            Kind::StringTooLong.into()
+        }
+
+        #[inline]
+        fn container_too_large() -> Self {
+            Kind::ContainerTooLarge.into()
+        }
+
+        #[inline]
+        fn alloc_limit_exceeded() -> Self {
+            Kind::AllocLimitExceeded.into()
+        }
+
+        #[inline]
+        fn duplicate_key() -> Self {
+            Kind::DuplicateKey.into()
+        }
+
+        #[inline]
+        #[cfg(feature = "std")]
+        fn io(err: std::io::Error) -> Self {
+            Kind::Io(err).into()
+        }
+
         #[inline]
         fn apply_span(&mut self, _: usize, _: usize) {}
+
+        #[inline]
+        #[allow(unused_variables)]
+        fn apply_offset(&mut self, offset: usize) {
+            #[cfg(feature = "alloc")]
+            {
+                self.repr.offset = Some(offset);
+            }
+        }
     }
 
     #[doc(hidden)]
@@ -1209,7 +1750,11 @@ const _: fn() = || {
         fn into(self) -> Error {
             Error {
                 #[cfg(feature = "alloc")]
-                kind: Box::new(self),
+                repr: Box::new(Repr {
+                    kind: self,
+                    path: String::new(),
+                    offset: None,
+                }),
                 #[cfg(not(feature = "alloc"))]
                 kind: self,
                 #[cfg(feature = "span")]
@@ -1219,6 +1764,24 @@ const _: fn() = || {
     }
 };
 
+// deserializes `T` and records where parsing stopped on failure.
+#[inline]
+fn deserialize<'a, S, C, T>(mut parser: Parser<'a, S, C>) -> Result<T>
+where
+    S: Source + 'a,
+    C: Config,
+    T: Deserialize<'a>,
+{
+    match T::deserialize(&mut parser) {
+        // a top-level scalar ends wherever the source stops, which may be due to an error
+        Ok(v) => match parser.take_io_error::<Error>() {
+            Some(e) => Err(e),
+            _ => Ok(v),
+        },
+        Err(e) => Err(parser.fail(e)),
+    }
+}
+
 /// Deserializes specified type from a JSON string input.
 ///
 /// # Errors
@@ -1240,7 +1803,7 @@ const _: fn() = || {
 /// ```
 #[inline]
 pub fn from_str<'a, T: Deserialize<'a>>(s: &'a str) -> Result<T> {
-    T::deserialize(&mut Parser::from_str(s))
+    deserialize(Parser::from_str(s))
 }
 
 /// Deserializes specified type from a JSON string input.
@@ -1262,7 +1825,7 @@ pub fn from_str<'a, T: Deserialize<'a>>(s: &'a str) -> Result<T> {
 /// ```
 #[inline]
 pub unsafe fn from_mut_str<'a, T: Deserialize<'a>>(s: &'a mut str) -> Result<T> {
-    T::deserialize(&mut Parser::from_mut_str(s))
+    deserialize(Parser::from_mut_str(s))
 }
 
 /// Deserializes specified type from a JSON byte input.
@@ -1270,7 +1833,7 @@ pub unsafe fn from_mut_str<'a, T: Deserialize<'a>>(s: &'a mut str) -> Result<T>
 /// Same as [`from_str`] but will perform UTF-8 validation.
 #[inline]
 pub fn from_slice<'a, T: Deserialize<'a>>(s: &'a [u8]) -> Result<T> {
-    T::deserialize(&mut Parser::from_slice(s))
+    deserialize(Parser::from_slice(s))
 }
 
 /// Deserializes specified type from a JSON byte input.
@@ -1278,7 +1841,7 @@ pub fn from_slice<'a, T: Deserialize<'a>>(s: &'a [u8]) -> Result<T> {
 /// Same as [`from_mut_str`] but will perform UTF-8 validation.
 #[inline]
 pub fn from_mut_slice<'a, T: Deserialize<'a>>(s: &'a mut [u8]) -> Result<T> {
-    T::deserialize(&mut Parser::from_mut_slice(s))
+    deserialize(Parser::from_mut_slice(s))
 }
 
 /// Deserializes specified type from a JSON byte input.
@@ -1286,7 +1849,7 @@ pub fn from_mut_slice<'a, T: Deserialize<'a>>(s: &'a mut [u8]) -> Result<T> {
 /// Same as [`from_str`] and will not perform UTF-8 validation.
 #[inline]
 pub unsafe fn from_slice_unchecked<'a, T: Deserialize<'a>>(s: &'a [u8]) -> Result<T> {
-    T::deserialize(&mut Parser::from_slice_unchecked(s))
+    deserialize(Parser::from_slice_unchecked(s))
 }
 
 /// Deserializes specified type from a JSON byte input.
@@ -1294,7 +1857,7 @@ pub unsafe fn from_slice_unchecked<'a, T: Deserialize<'a>>(s: &'a [u8]) -> Resul
 /// Same as [`from_mut_str`] and will not perform UTF-8 validation.
 #[inline]
 pub unsafe fn from_mut_slice_unchecked<'a, T: Deserialize<'a>>(s: &'a mut [u8]) -> Result<T> {
-    T::deserialize(&mut Parser::from_mut_slice_unchecked(s))
+    deserialize(Parser::from_mut_slice_unchecked(s))
 }
 
 /// Deserializes specified type from null padded JSON input.
@@ -1303,7 +1866,7 @@ pub unsafe fn from_mut_slice_unchecked<'a, T: Deserialize<'a>>(s: &'a mut [u8])
 #[inline]
 #[cfg(feature = "alloc")]
 pub fn from_null_padded<'a, T: Deserialize<'a>>(buf: &'a NullPadded) -> Result<T> {
-    T::deserialize(&mut Parser::new(buf))
+    deserialize(Parser::new(buf))
 }
 
 /// Deserializes specified type from null padded JSON input.
@@ -1312,7 +1875,7 @@ pub fn from_null_padded<'a, T: Deserialize<'a>>(buf: &'a NullPadded) -> Result<T
 #[inline]
 #[cfg(feature = "alloc")]
 pub fn from_mut_null_padded<'a, T: Deserialize<'a>>(buf: &'a mut NullPadded) -> Result<T> {
-    T::deserialize(&mut Parser::new(buf))
+    deserialize(Parser::new(buf))
 }
 
 /// Deserializes specified type from a streaming source.
@@ -1338,7 +1901,7 @@ where
     R: Read,
     T: de::DeserializeOwned,
 {
-    T::deserialize(&mut Parser::from_reader(r))
+    deserialize(Parser::from_reader(r))
 }
 
 /// Deserializes specified type from a streaming source.
@@ -1351,7 +1914,190 @@ where
     R: Read,
     T: de::DeserializeOwned,
 {
-    T::deserialize(&mut Parser::from_reader_unchecked(r))
+    deserialize(Parser::from_reader_unchecked(r))
+}
+
+/// Deserializes successive values of the specified type from a streaming source.
+///
+/// Reads newline delimited JSON (NDJSON / JSON Lines) or any whitespace separated
+/// values incrementally from any type implementing [`Read`]. See [`StreamDeserializer`].
+///
+/// # Example
+/// ```no_run
+/// use serde::Deserialize;
+/// use std::fs::File;
+///
+/// #[derive(Deserialize)]
+/// struct Log {
+///     level: String,
+///     msg: String,
+/// }
+///
+/// let mut iter = flexon::from_reader_iter::<_, Log>(File::open("logs.ndjson")?);
+/// while let Some(log) = iter.next() {
+///     let log = log?;
+///     println!("{} at byte {}: {}", log.level, iter.offset(), log.msg);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+pub fn from_reader_iter<'a, R, T>(r: R) -> StreamDeserializer<'a, Reader<false, R>, CTConfig, T>
+where
+    R: Read + 'a,
+    T: de::DeserializeOwned,
+{
+    StreamDeserializer::new(Parser::from_reader(r))
+}
+
+/// Iterator that deserializes successive top-level values.
+///
+/// Values may be separated by any amount of whitespace. Consumed data is trimmed
+/// from volatile sources as it goes. The iterator stops after the first error.
+///
+/// # Example
+/// ```
+/// use flexon::{Parser, serde::de::StreamDeserializer};
+///
+/// let parser = Parser::from_str("[1, 2]\n[3]\n");
+/// let vals: Vec<Vec<u8>> = StreamDeserializer::new(parser).collect::<Result<_, _>>()?;
+///
+/// assert_eq!(vals, [vec![1, 2], vec![3]]);
+/// # Ok::<(), flexon::serde::de::Error>(())
+/// ```
+pub struct StreamDeserializer<'a, S: Source + 'a, C: Config, T> {
+    parser: Parser<'a, S, C>,
+    offset: usize,
+    done: bool,
+    __: PhantomData<T>,
+}
+
+impl<'a, S: Source + 'a, C: Config, T: Deserialize<'a>> StreamDeserializer<'a, S, C, T> {
+    /// Creates a stream deserializer with the given parser.
+    #[inline]
+    pub fn new(parser: Parser<'a, S, C>) -> Self {
+        Self {
+            parser,
+            offset: 0,
+            done: false,
+            __: PhantomData,
+        }
+    }
+
+    /// Returns the starting byte offset of the most recently yielded value.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, S: Source + 'a, C: Config, T: Deserialize<'a>> Iterator
+    for StreamDeserializer<'a, S, C, T>
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let parser = &mut self.parser;
+        if parser.skip_whitespace() == 0 {
+            self.done = true;
+            return parser.take_io_error().map(Err);
+        }
+
+        self.offset = parser.idx();
+        parser.allocated = 0;
+        if S::Volatility::IS_VOLATILE {
+            parser.src.trim(self.offset);
+        }
+
+        parser.dec();
+        Some(T::deserialize(&mut *parser).map_err(|e| {
+            self.done = true;
+            parser.fail(e)
+        }))
+    }
+}
+
+/// Iterator that deserializes the elements of a JSON array one at a time.
+///
+/// Meant to be used after [`Parser::skip_to`] to stream a large array without deserializing
+/// the whole container. Consumed data is trimmed from volatile sources as it goes, keeping the
+/// memory usage bounded by the largest element. The iterator stops after the first error.
+///
+/// # Example
+/// ```
+/// use flexon::{Parser, serde::de::{ArrayDeserializer, Error}};
+///
+/// let src = r#"{"meta": {}, "items": [[1, 2], [3]]}"#;
+/// let mut parser = Parser::from_str(src);
+///
+/// parser.skip_to::<Error, _>(["items"])?;
+/// let items: Vec<Vec<u8>> = ArrayDeserializer::new(&mut parser).collect::<Result<_, _>>()?;
+///
+/// assert_eq!(items, [vec![1, 2], vec![3]]);
+/// # Ok::<(), flexon::serde::de::Error>(())
+/// ```
+pub struct ArrayDeserializer<'p, 'a, S: Source + 'a, C: Config, T> {
+    parser: &'p mut Parser<'a, S, C>,
+    offset: usize,
+    started: bool,
+    done: bool,
+    __: PhantomData<T>,
+}
+
+impl<'p, 'a, S: Source + 'a, C: Config, T: Deserialize<'a>> ArrayDeserializer<'p, 'a, S, C, T> {
+    /// Creates an array deserializer with the given parser.
+    #[inline]
+    pub fn new(parser: &'p mut Parser<'a, S, C>) -> Self {
+        Self {
+            parser,
+            offset: 0,
+            started: false,
+            done: false,
+            __: PhantomData,
+        }
+    }
+
+    /// Returns the starting byte offset of the most recently yielded element.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, S: Source + 'a, C: Config, T: Deserialize<'a>> Iterator
+    for ArrayDeserializer<'_, 'a, S, C, T>
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let parser = &mut *self.parser;
+        match parser.next_element(self.started) {
+            Ok(true) => self.started = true,
+            Ok(false) => {
+                self.done = true;
+                return None;
+            }
+            Err(e) => {
+                self.done = true;
+                return Some(Err(parser.fail(e)));
+            }
+        }
+
+        self.offset = parser.idx().wrapping_add(1);
+        parser.allocated = 0;
+        Some(T::deserialize(&mut *parser).map_err(|e| {
+            self.done = true;
+            parser.fail(e)
+        }))
+    }
 }
 
 /// Skips to the given path and deserializes the type using the provided parser.
@@ -1378,9 +2124,12 @@ where
     P: IntoIterator,
     P::Item: JsonPointer,
 {
-    parser._skip_to(path)?;
+    if let Err(e) = parser._skip_to(path) {
+        return Err(parser.fail(e));
+    }
+
     parser.dec_if_not_empty();
-    T::deserialize(parser)
+    T::deserialize(&mut *parser).map_err(|e| parser.fail(e))
 }
 
 /// Skips to the given path and deserializes the type using the provided parser.
@@ -1,10 +1,7 @@
 //! Formats for JSON serialization.
 
-use crate::{
-    misc::Sealed,
-    serde::ser::{Error, Result},
-};
-use std::io::Write;
+use crate::misc::Sealed;
+use std::io::{Result, Write};
 
 /// Controls how JSON output is formatted.
 pub trait Format: Sealed + Sized {
@@ -80,22 +77,15 @@ impl Format for Pretty<'_> {
 
     #[inline(always)]
     fn sep(&self, s: &mut impl Write) -> Result<()> {
-        match s.write(b" ") {
-            Ok(_) => Ok(()),
-            _ => Err(Error),
-        }
+        s.write_all(b" ")
     }
 
     #[inline(always)]
     fn indent(&self, s: &mut impl Write) -> Result<()> {
-        if s.write(b"\n").is_err() {
-            return Err(Error);
-        }
+        s.write_all(b"\n")?;
 
         for _ in 0..self.depth {
-            if s.write(self.indent.as_bytes()).is_err() {
-                return Err(Error);
-            }
+            s.write_all(self.indent.as_bytes())?;
         }
 
         Ok(())
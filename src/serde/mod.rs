@@ -3,6 +3,8 @@
 pub mod de;
 mod unchecked;
 mod value;
+#[cfg(feature = "alloc")]
+mod value_de;
 
 #[cfg(feature = "std")]
 pub mod format;
@@ -19,11 +21,16 @@ pub use de::{
 
 #[doc(inline)]
 #[cfg(feature = "alloc")]
-pub use de::{from_mut_null_padded, from_null_padded};
+pub use {
+    de::{from_mut_null_padded, from_null_padded},
+    value_de::from_value,
+};
 
 #[doc(inline)]
 #[cfg(feature = "std")]
 pub use {
-    de::{from_reader, from_reader_unchecked},
-    ser::{to_string, to_string_pretty, to_vec, to_vec_pretty, to_writer, to_writer_pretty},
+    de::{from_reader, from_reader_iter, from_reader_unchecked},
+    ser::{
+        Serializer, to_string, to_string_pretty, to_vec, to_vec_pretty, to_writer, to_writer_pretty,
+    },
 };
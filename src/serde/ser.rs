@@ -11,9 +11,66 @@ use std::io::Write;
 pub type Result<T> = core::result::Result<T, Error>;
 
 /// JSON serializing structure.
-pub struct Serializer<W: Write, F: Format>(W, F);
+pub struct Serializer<W: Write, F: Format>(W, F, bool);
+
+impl<W: Write> Serializer<W, Compact> {
+    /// Creates a serializer that writes compact JSON into the given writer.
+    #[inline]
+    pub fn new(w: W) -> Self {
+        Self(w, Compact, false)
+    }
+}
 
 impl<W: Write, F: Format> Serializer<W, F> {
+    /// Creates a serializer that writes JSON into the given writer using the given format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flexon::serde::{Serializer, format::Pretty};
+    /// use serde::Serialize;
+    ///
+    /// let mut buf = Vec::new();
+    /// let mut ser = Serializer::with_format(&mut buf, Pretty::with_indent("\t"));
+    ///
+    /// [1, 2].serialize(&mut ser).unwrap();
+    /// assert_eq!(buf, b"[\n\t1,\n\t2\n]");
+    /// ```
+    #[inline]
+    pub fn with_format(w: W, f: F) -> Self {
+        Self(w, f, false)
+    }
+
+    /// Whether to write infinite and NaN floats as `Infinity`, `-Infinity` and `NaN`.
+    ///
+    /// The output is no longer valid JSON, but it can be read back by a parser with
+    /// [`Dialect::NON_FINITE`](crate::config::Dialect::NON_FINITE) enabled. Otherwise
+    /// they are written as `null`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flexon::serde::Serializer;
+    /// use serde::Serialize;
+    ///
+    /// let mut buf = Vec::new();
+    /// let mut ser = Serializer::new(&mut buf).allow_non_finite(true);
+    ///
+    /// [f64::NAN, f64::NEG_INFINITY].serialize(&mut ser).unwrap();
+    /// assert_eq!(buf, b"[NaN,-Infinity]");
+    /// ```
+    #[inline]
+    pub fn allow_non_finite(mut self, v: bool) -> Self {
+        self.2 = v;
+        self
+    }
+
+    /// Returns the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+
     #[inline(always)]
     pub(super) fn write(&mut self, v: char) -> Result<()> {
         match self.0.write(&[v as _]) {
@@ -30,6 +87,16 @@ impl<W: Write, F: Format> Serializer<W, F> {
         }
     }
 
+    #[cold]
+    fn non_finite(&mut self, v: f64) -> Result<()> {
+        self.write_n(match self.2 {
+            true if v.is_nan() => b"NaN",
+            true if v > 0.0 => b"Infinity",
+            true => b"-Infinity",
+            _ => b"null",
+        })
+    }
+
     #[inline(always)]
     fn comma(&mut self, flag: &mut bool) -> Result<()> {
         match *flag {
@@ -103,28 +170,22 @@ impl<'a, W: Write, F: Format> ser::Serializer for &'a mut Serializer<W, F> {
     }
 
     fn serialize_f32(self, v: f32) -> Result<()> {
-        match match v.is_finite() {
+        match v.is_finite() {
             true => {
                 let mut tmp = zmij::Buffer::new();
-                self.0.write(tmp.format_finite(v).as_bytes())
+                self.write_n(tmp.format_finite(v).as_bytes())
             }
-            _ => self.0.write(b"null"),
-        } {
-            Ok(_) => Ok(()),
-            _ => Err(Error),
+            _ => self.non_finite(v as _),
         }
     }
 
     fn serialize_f64(self, v: f64) -> Result<()> {
-        match match v.is_finite() {
+        match v.is_finite() {
             true => {
                 let mut tmp = zmij::Buffer::new();
-                self.0.write(tmp.format_finite(v).as_bytes())
+                self.write_n(tmp.format_finite(v).as_bytes())
             }
-            _ => self.0.write(b"null"),
-        } {
-            Ok(_) => Ok(()),
-            _ => Err(Error),
+            _ => self.non_finite(v as _),
         }
     }
 
@@ -546,28 +607,24 @@ impl<W: Write, F: Format> ser::Serializer for MapKey<'_, W, F> {
 
     fn serialize_f32(self, v: f32) -> Result<()> {
         self.0.write('"')?;
-        if let Err(_) = match v.is_finite() {
+        match v.is_finite() {
             true => {
                 let mut tmp = zmij::Buffer::new();
-                self.0.write_n(tmp.format_finite(v).as_bytes())
+                self.0.write_n(tmp.format_finite(v).as_bytes())?
             }
-            _ => self.0.write_n(b"null"),
-        } {
-            return Err(Error);
+            _ => self.0.non_finite(v as _)?,
         }
         self.0.write('"')
     }
 
     fn serialize_f64(self, v: f64) -> Result<()> {
         self.0.write('"')?;
-        if let Err(_) = match v.is_finite() {
+        match v.is_finite() {
             true => {
                 let mut tmp = zmij::Buffer::new();
-                self.0.write_n(tmp.format_finite(v).as_bytes())
+                self.0.write_n(tmp.format_finite(v).as_bytes())?
             }
-            _ => self.0.write_n(b"null"),
-        } {
-            return Err(Error);
+            _ => self.0.non_finite(v as _)?,
         }
         self.0.write('"')
     }
@@ -698,6 +755,13 @@ impl Display for Error {
 
 impl core::error::Error for Error {}
 
+impl From<std::io::Error> for Error {
+    #[inline]
+    fn from(_: std::io::Error) -> Self {
+        Self
+    }
+}
+
 /// Serializes the given data into byte vector as a JSON.
 ///
 /// # Errors
@@ -706,7 +770,7 @@ impl core::error::Error for Error {}
 #[inline]
 pub fn to_vec<T: Serialize>(v: T) -> Result<Vec<u8>> {
     let mut tmp = Vec::new();
-    v.serialize(&mut Serializer(&mut tmp, Compact))?;
+    v.serialize(&mut Serializer(&mut tmp, Compact, false))?;
     Ok(tmp)
 }
 
@@ -718,7 +782,7 @@ pub fn to_vec<T: Serialize>(v: T) -> Result<Vec<u8>> {
 #[inline]
 pub fn to_vec_pretty<T: Serialize>(v: T) -> Result<Vec<u8>> {
     let mut tmp = Vec::new();
-    v.serialize(&mut Serializer(&mut tmp, Pretty::new()))?;
+    v.serialize(&mut Serializer(&mut tmp, Pretty::new(), false))?;
     Ok(tmp)
 }
 
@@ -730,7 +794,7 @@ pub fn to_vec_pretty<T: Serialize>(v: T) -> Result<Vec<u8>> {
 #[inline]
 pub fn to_string<T: Serialize>(v: T) -> Result<String> {
     let mut tmp = String::new();
-    unsafe { v.serialize(&mut Serializer(tmp.as_mut_vec(), Compact))? }
+    unsafe { v.serialize(&mut Serializer(tmp.as_mut_vec(), Compact, false))? }
     Ok(tmp)
 }
 
@@ -742,7 +806,7 @@ pub fn to_string<T: Serialize>(v: T) -> Result<String> {
 #[inline]
 pub fn to_string_pretty<T: Serialize>(v: T) -> Result<String> {
     let mut tmp = String::new();
-    unsafe { v.serialize(&mut Serializer(tmp.as_mut_vec(), Pretty::new()))? }
+    unsafe { v.serialize(&mut Serializer(tmp.as_mut_vec(), Pretty::new(), false))? }
     Ok(tmp)
 }
 
@@ -754,7 +818,7 @@ pub fn to_string_pretty<T: Serialize>(v: T) -> Result<String> {
 /// non-string map keys, or an I/O error occurs while writing.
 #[inline]
 pub fn to_writer<W: Write, T: Serialize>(w: W, v: T) -> Result<()> {
-    v.serialize(&mut Serializer(w, Compact))
+    v.serialize(&mut Serializer(w, Compact, false))
 }
 
 /// Serializes the given data into the provided writer as a pretty-printed JSON.
@@ -765,5 +829,5 @@ pub fn to_writer<W: Write, T: Serialize>(w: W, v: T) -> Result<()> {
 /// non-string map keys, or an I/O error occurs while writing.
 #[inline]
 pub fn to_writer_pretty<W: Write, T: Serialize>(w: W, v: T) -> Result<()> {
-    v.serialize(&mut Serializer(w, Pretty::new()))
+    v.serialize(&mut Serializer(w, Pretty::new(), false))
 }
@@ -202,7 +202,10 @@ impl<'de> Deserialize<'de> for OwnedValue {
 }
 
 #[cfg(all(feature = "alloc", feature = "span"))]
-impl<'de, S: Deserialize<'de>> Deserialize<'de> for GenericValue<S> {
+impl<'de, S> Deserialize<'de> for GenericValue<S>
+where
+    S: Deserialize<'de> + Deref<Target = str>,
+{
     #[inline]
     fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
         use core::marker::PhantomData;
@@ -210,7 +213,10 @@ impl<'de, S: Deserialize<'de>> Deserialize<'de> for GenericValue<S> {
 
         struct Visitor<S>(PhantomData<S>);
 
-        impl<'de, S: Deserialize<'de>> de::Visitor<'de> for Visitor<S> {
+        impl<'de, S> de::Visitor<'de> for Visitor<S>
+        where
+            S: Deserialize<'de> + Deref<Target = str>,
+        {
             type Value = GenericValue<S>;
 
             fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -0,0 +1,687 @@
+//! Deserialize from already parsed JSON values.
+
+use crate::{
+    Parser,
+    serde::de::{Error, Result},
+    value::{
+        LazyValue, Number, Object, OwnedValue,
+        array::IntoIter,
+        borrowed::{String, Value},
+        lazy,
+        number::Kind,
+        owned,
+    },
+};
+use core::{ops::Deref, slice::Iter};
+use serde_core::{
+    Deserialize, Deserializer,
+    de::{
+        DeserializeSeed, EnumAccess, Error as _, MapAccess, SeqAccess, Unexpected, VariantAccess,
+        Visitor,
+    },
+    forward_to_deserialize_any,
+};
+
+#[cfg(feature = "span")]
+use {
+    super::span::TOKEN,
+    crate::span::{GenericValue, Span},
+    serde_core::de::IntoDeserializer,
+};
+
+/// Deserializes specified type from a JSON value.
+///
+/// Accepts [`Value`](crate::Value), [`OwnedValue`], [`LazyValue`] and `span::Value`, either by
+/// value or by reference. Strings are borrowed whenever the value allows it, and raw parts of a
+/// [`LazyValue`] are parsed only as they are deserialized.
+///
+/// # Errors
+/// Returns an error if the value cannot be deserialized into type `T`.
+///
+/// # Example
+/// ```
+/// use flexon::Value;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Circle<'a> {
+///     color: &'a str,
+///     radius: f64,
+/// }
+///
+/// let src = r#"{"type": "circle", "color": "red", "radius": 2.5}"#;
+/// let val: Value = flexon::parse(src)?;
+///
+/// if val["type"].as_str() == Some("circle") {
+///     let circle: Circle = flexon::from_value(&val)?;
+///
+///     assert_eq!(circle.color, "red");
+///     assert_eq!(circle.radius, 2.5);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[inline]
+pub fn from_value<'de, T: Deserialize<'de>>(
+    val: impl Deserializer<'de, Error = Error>,
+) -> Result<T> {
+    T::deserialize(val)
+}
+
+// a value that can drive the deserialization of its own kind.
+trait Node<'de>: Deserializer<'de, Error = Error> {
+    type Str: Str<'de>;
+    type Key: Str<'de>;
+    type Array: Iterator<Item = Self>;
+    type Object: Iterator<Item = (Self::Key, Self)>;
+
+    fn unpack(self) -> Unpacked<'de, Self>;
+
+    fn is_null(&self) -> bool;
+
+    #[inline]
+    #[cfg(feature = "span")]
+    fn span(&self) -> Option<[usize; 2]> {
+        None
+    }
+}
+
+enum Unpacked<'de, N: Node<'de>> {
+    Null,
+    Raw(&'de str),
+    Array(N::Array),
+    Object(N::Object),
+    String(N::Str),
+    Number(Number),
+    Boolean(bool),
+}
+
+// a string that is visited as borrowed whenever it outlives the deserializer.
+trait Str<'de> {
+    fn visit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value>;
+}
+
+impl<'de, S: Deref<Target = str>> Str<'de> for &'de S {
+    #[inline]
+    fn visit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_str(self)
+    }
+}
+
+impl<'de> Str<'de> for String<'de> {
+    #[inline]
+    fn visit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.as_borrowed() {
+            Some(v) => visitor.visit_borrowed_str(v),
+            _ => visitor.visit_string(self.into()),
+        }
+    }
+}
+
+impl<'de> Str<'de> for owned::String {
+    #[inline]
+    fn visit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_str(&self)
+    }
+}
+
+#[cfg(feature = "span")]
+impl<'de, S: Str<'de>> Str<'de> for Span<S> {
+    #[inline]
+    fn visit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.into_data().visit(visitor)
+    }
+}
+
+fn visit<'de, N: Node<'de>, V: Visitor<'de>>(node: N, visitor: V) -> Result<V::Value> {
+    #[cfg(feature = "span")]
+    let span = node.span();
+
+    let res = match node.unpack() {
+        Unpacked::Null => visitor.visit_unit(),
+        Unpacked::Raw(v) => (&mut Parser::from_str(v)).deserialize_any(visitor),
+        Unpacked::Array(v) => {
+            let mut seq = Elements { iter: v, len: 0 };
+            let res = visitor.visit_seq(&mut seq)?;
+            seq.end().map(|_| res)
+        }
+        Unpacked::Object(v) => {
+            let mut map = Members {
+                iter: v,
+                val: None,
+                len: 0,
+            };
+            let res = visitor.visit_map(&mut map)?;
+            map.end().map(|_| res)
+        }
+        Unpacked::String(v) => v.visit(visitor),
+        Unpacked::Number(v) => match v.0 {
+            Kind::Unsigned(v) => visitor.visit_u64(v),
+            Kind::Signed(v) => visitor.visit_i64(v),
+            Kind::Float(v) => visitor.visit_f64(v),
+        },
+        Unpacked::Boolean(v) => visitor.visit_bool(v),
+    };
+
+    #[cfg(feature = "span")]
+    let res = with_span(res, span);
+    res
+}
+
+#[inline]
+fn visit_option<'de, N: Node<'de>, V: Visitor<'de>>(node: N, visitor: V) -> Result<V::Value> {
+    match node.is_null() {
+        true => visitor.visit_none(),
+        _ => visitor.visit_some(node),
+    }
+}
+
+#[inline]
+fn visit_newtype_struct<'de, N: Node<'de>, V: Visitor<'de>>(
+    node: N,
+    _name: &'static str,
+    visitor: V,
+) -> Result<V::Value> {
+    #[cfg(feature = "span")]
+    if _name == TOKEN
+        && let Some([start, end]) = node.span()
+    {
+        return visitor.visit_seq(Spanned {
+            node: Some(node),
+            start,
+            end,
+            state: 0,
+        });
+    }
+
+    visitor.visit_newtype_struct(node)
+}
+
+fn visit_enum<'de, N: Node<'de>, V: Visitor<'de>>(
+    node: N,
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+) -> Result<V::Value> {
+    #[cfg(feature = "span")]
+    let span = node.span();
+
+    let res = match node.unpack() {
+        Unpacked::String(v) => visitor.visit_enum(Variant {
+            key: v,
+            val: None::<N>,
+        }),
+        Unpacked::Object(mut v) => match (v.next(), v.next()) {
+            (Some((key, val)), None) => visitor.visit_enum(Variant {
+                key,
+                val: Some(val),
+            }),
+            _ => Err(Error::invalid_value(
+                Unexpected::Map,
+                &"map with a single key",
+            )),
+        },
+        Unpacked::Raw(v) => (&mut Parser::from_str(v)).deserialize_enum(name, variants, visitor),
+        Unpacked::Null => Err(Error::invalid_type(Unexpected::Unit, &"string or map")),
+        Unpacked::Array(_) => Err(Error::invalid_type(Unexpected::Seq, &"string or map")),
+        Unpacked::Number(v) => Err(Error::invalid_type(
+            match v.0 {
+                Kind::Unsigned(v) => Unexpected::Unsigned(v),
+                Kind::Signed(v) => Unexpected::Signed(v),
+                Kind::Float(v) => Unexpected::Float(v),
+            },
+            &"string or map",
+        )),
+        Unpacked::Boolean(v) => Err(Error::invalid_type(Unexpected::Bool(v), &"string or map")),
+    };
+
+    #[cfg(feature = "span")]
+    let res = with_span(res, span);
+    res
+}
+
+// attributes the error to the innermost spanned value it occurred in.
+#[inline]
+#[cfg(feature = "span")]
+fn with_span<T>(res: Result<T>, span: Option<[usize; 2]>) -> Result<T> {
+    match (res, span) {
+        (Err(mut err), Some(span)) if err.span == [0; 2] => {
+            err.span = span;
+            Err(err)
+        }
+        (res, _) => res,
+    }
+}
+
+macro_rules! impl_deserializer {
+    ([$($gen:tt)*] $ty:ty) => {
+        impl<$($gen)*> Deserializer<'de> for $ty {
+            type Error = Error;
+
+            #[inline]
+            fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                visit(self, visitor)
+            }
+
+            #[inline]
+            fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                visit_option(self, visitor)
+            }
+
+            #[inline]
+            fn deserialize_newtype_struct<V: Visitor<'de>>(
+                self,
+                name: &'static str,
+                visitor: V,
+            ) -> Result<V::Value> {
+                visit_newtype_struct(self, name, visitor)
+            }
+
+            #[inline]
+            fn deserialize_enum<V: Visitor<'de>>(
+                self,
+                name: &'static str,
+                variants: &'static [&'static str],
+                visitor: V,
+            ) -> Result<V::Value> {
+                visit_enum(self, name, variants, visitor)
+            }
+
+            #[inline]
+            fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                visitor.visit_unit()
+            }
+
+            forward_to_deserialize_any! {
+                bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+                bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+            }
+        }
+    };
+}
+
+macro_rules! value_node {
+    (
+        $value:ident,
+        [$($gen:tt)*] $ty:ty, $str:ty,
+        [$($ref_gen:tt)*] $ref_ty:ty, $ref_str:ty $(,)?
+    ) => {
+        impl<$($gen)*> Node<'de> for $ty {
+            type Str = $str;
+            type Key = $str;
+            type Array = IntoIter<Self>;
+            type Object = IntoIter<($str, Self)>;
+
+            #[inline]
+            fn unpack(self) -> Unpacked<'de, Self> {
+                match self {
+                    $value::Null => Unpacked::Null,
+                    $value::Array(v) => Unpacked::Array(v.into_iter()),
+                    $value::Object(v) => Unpacked::Object(v.into_iter()),
+                    $value::String(v) => Unpacked::String(v),
+                    $value::Number(v) => Unpacked::Number(v),
+                    $value::Boolean(v) => Unpacked::Boolean(v),
+                }
+            }
+
+            #[inline]
+            fn is_null(&self) -> bool {
+                $value::is_null(self)
+            }
+        }
+
+        impl<$($ref_gen)*> Node<'de> for &'de $ref_ty {
+            type Str = &'de $ref_str;
+            type Key = &'de $ref_str;
+            type Array = Iter<'de, $ref_ty>;
+            type Object = <&'de Object<$ref_str, $ref_ty> as IntoIterator>::IntoIter;
+
+            #[inline]
+            fn unpack(self) -> Unpacked<'de, Self> {
+                match self {
+                    $value::Null => Unpacked::Null,
+                    $value::Array(v) => Unpacked::Array(v.into_iter()),
+                    $value::Object(v) => Unpacked::Object(v.into_iter()),
+                    $value::String(v) => Unpacked::String(v),
+                    $value::Number(v) => Unpacked::Number(*v),
+                    $value::Boolean(v) => Unpacked::Boolean(*v),
+                }
+            }
+
+            #[inline]
+            fn is_null(&self) -> bool {
+                $value::is_null(self)
+            }
+        }
+
+        impl_deserializer!([$($gen)*] $ty);
+        impl_deserializer!([$($ref_gen)*] &'de $ref_ty);
+    };
+}
+
+value_node!(
+    Value,
+    ['de] Value<'de>, String<'de>,
+    ['de, 'a: 'de] Value<'a>, String<'a>,
+);
+
+value_node!(
+    OwnedValue,
+    ['de] OwnedValue, owned::String,
+    ['de] OwnedValue, owned::String,
+);
+
+impl<'de> Node<'de> for LazyValue<'de> {
+    type Str = String<'de>;
+    type Key = String<'de>;
+    type Array = lazy::IntoValues<'de>;
+    type Object = lazy::IntoMembers<'de>;
+
+    #[inline]
+    fn unpack(self) -> Unpacked<'de, Self> {
+        match self {
+            LazyValue::Null => Unpacked::Null,
+            LazyValue::Raw(v) => Unpacked::Raw(v.0),
+            LazyValue::Array(v) => Unpacked::Array(v.into_values()),
+            LazyValue::Object(v) => Unpacked::Object(v.into_members()),
+            LazyValue::String(v) => Unpacked::String(v),
+            LazyValue::Number(v) => Unpacked::Number(v),
+            LazyValue::Boolean(v) => Unpacked::Boolean(v),
+        }
+    }
+
+    #[inline]
+    fn is_null(&self) -> bool {
+        LazyValue::is_null(self)
+    }
+}
+
+impl_deserializer!(['de] LazyValue<'de>);
+
+#[cfg(feature = "span")]
+impl<'de, S: Str<'de>> Node<'de> for Span<GenericValue<S>> {
+    type Str = S;
+    type Key = Span<S>;
+    type Array = IntoIter<Self>;
+    type Object = IntoIter<(Span<S>, Self)>;
+
+    #[inline]
+    fn unpack(self) -> Unpacked<'de, Self> {
+        match self.into_data() {
+            GenericValue::Null => Unpacked::Null,
+            GenericValue::Array(v) => Unpacked::Array(v.into_iter()),
+            GenericValue::Object(v) => Unpacked::Object(v.into_iter()),
+            GenericValue::String(v) => Unpacked::String(v),
+            GenericValue::Number(v) => Unpacked::Number(v),
+            GenericValue::Boolean(v) => Unpacked::Boolean(v),
+        }
+    }
+
+    #[inline]
+    fn is_null(&self) -> bool {
+        self.data().as_null().is_some()
+    }
+
+    #[inline]
+    fn span(&self) -> Option<[usize; 2]> {
+        Some([self.start(), self.end()])
+    }
+}
+
+#[cfg(feature = "span")]
+impl<'de, S: Deref<Target = str> + 'de> Node<'de> for &'de Span<GenericValue<S>> {
+    type Str = &'de S;
+    type Key = &'de Span<S>;
+    type Array = Iter<'de, Span<GenericValue<S>>>;
+    type Object = <&'de Object<Span<S>, Span<GenericValue<S>>> as IntoIterator>::IntoIter;
+
+    #[inline]
+    fn unpack(self) -> Unpacked<'de, Self> {
+        match self.data() {
+            GenericValue::Null => Unpacked::Null,
+            GenericValue::Array(v) => Unpacked::Array(v.into_iter()),
+            GenericValue::Object(v) => Unpacked::Object(v.into_iter()),
+            GenericValue::String(v) => Unpacked::String(v),
+            GenericValue::Number(v) => Unpacked::Number(*v),
+            GenericValue::Boolean(v) => Unpacked::Boolean(*v),
+        }
+    }
+
+    #[inline]
+    fn is_null(&self) -> bool {
+        self.data().as_null().is_some()
+    }
+
+    #[inline]
+    fn span(&self) -> Option<[usize; 2]> {
+        Some([self.start(), self.end()])
+    }
+}
+
+#[cfg(feature = "span")]
+impl_deserializer!(['de, S: Str<'de>] Span<GenericValue<S>>);
+
+#[cfg(feature = "span")]
+impl_deserializer!(['de, S: Deref<Target = str> + 'de] &'de Span<GenericValue<S>>);
+
+struct Elements<I> {
+    iter: I,
+    len: usize,
+}
+
+impl<'de, N: Node<'de>, I: Iterator<Item = N>> Elements<I> {
+    #[inline]
+    fn end(self) -> Result<()> {
+        match self.iter.count() {
+            0 => Ok(()),
+            rest => Err(Error::invalid_length(
+                self.len + rest,
+                &"fewer elements in array",
+            )),
+        }
+    }
+}
+
+impl<'de, N: Node<'de>, I: Iterator<Item = N>> SeqAccess<'de> for Elements<I> {
+    type Error = Error;
+
+    #[inline]
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.iter.next() {
+            Some(v) => {
+                self.len += 1;
+                seed.deserialize(v).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        match self.iter.size_hint() {
+            (lo, Some(hi)) if lo == hi => Some(lo),
+            _ => None,
+        }
+    }
+}
+
+struct Members<I, N> {
+    iter: I,
+    val: Option<N>,
+    len: usize,
+}
+
+impl<'de, N: Node<'de>, I: Iterator<Item = (N::Key, N)>> Members<I, N> {
+    #[inline]
+    fn end(self) -> Result<()> {
+        match self.iter.count() {
+            0 => Ok(()),
+            rest => Err(Error::invalid_length(
+                self.len + rest,
+                &"fewer elements in map",
+            )),
+        }
+    }
+}
+
+impl<'de, N: Node<'de>, I: Iterator<Item = (N::Key, N)>> MapAccess<'de> for Members<I, N> {
+    type Error = Error;
+
+    #[inline]
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.iter.next() {
+            Some((key, val)) => {
+                self.len += 1;
+                self.val = Some(val);
+                seed.deserialize(Key(key)).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        match self.val.take() {
+            Some(v) => seed.deserialize(v),
+            _ => Err(Error::custom("value is missing")),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        match self.iter.size_hint() {
+            (lo, Some(hi)) if lo == hi => Some(lo),
+            _ => None,
+        }
+    }
+}
+
+// deserializes object keys, which are always strings.
+struct Key<S>(S);
+
+impl<'de, S: Str<'de>> Deserializer<'de> for Key<S> {
+    type Error = Error;
+
+    #[inline]
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.0.visit(visitor)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(Variant {
+            key: self.0,
+            val: None::<Self>,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+// an enum variant, which is either a string or an object with a single member.
+struct Variant<S, D> {
+    key: S,
+    val: Option<D>,
+}
+
+impl<'de, S: Str<'de>, D: Deserializer<'de, Error = Error>> EnumAccess<'de> for Variant<S, D> {
+    type Error = Error;
+    type Variant = Payload<D>;
+
+    #[inline]
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Payload<D>)> {
+        Ok((seed.deserialize(Key(self.key))?, Payload(self.val)))
+    }
+}
+
+struct Payload<D>(Option<D>);
+
+impl<'de, D: Deserializer<'de, Error = Error>> VariantAccess<'de> for Payload<D> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.0 {
+            Some(v) => Deserialize::deserialize(v),
+            _ => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        match self.0 {
+            Some(v) => seed.deserialize(v),
+            _ => Err(Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            Some(v) => v.deserialize_seq(visitor),
+            _ => Err(Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.0 {
+            Some(v) => v.deserialize_map(visitor),
+            _ => Err(Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+// yields the starting offset, the value and the ending offset of a spanned value.
+#[cfg(feature = "span")]
+struct Spanned<N> {
+    node: Option<N>,
+    start: usize,
+    end: usize,
+    state: u8,
+}
+
+#[cfg(feature = "span")]
+impl<'de, N: Node<'de>> SeqAccess<'de> for Spanned<N> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        self.state += 1;
+
+        match self.state {
+            1 => seed.deserialize(self.start.into_deserializer()).map(Some),
+            2 => match self.node.take() {
+                Some(v) => seed.deserialize(v).map(Some),
+                _ => Ok(None),
+            },
+            3 => seed.deserialize(self.end.into_deserializer()).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
@@ -12,11 +12,20 @@ use core::{
         _mm_shuffle_epi8, _mm_slli_si128, _mm_storeu_si128, _mm_sub_epi8, _mm_subs_epu8,
         _mm_xor_si128, _mm256_and_si256, _mm256_cmpeq_epi8, _mm256_loadu_si256,
         _mm256_movemask_epi8, _mm256_set1_epi8, _mm256_setzero_si256, _mm256_sub_epi8,
-        _mm256_zeroupper,
+        _mm256_zeroupper, _mm512_and_si512, _mm512_cmpeq_epi8_mask, _mm512_cmplt_epu8_mask,
+        _mm512_loadu_si512, _mm512_set1_epi8, _mm512_setzero_si512, _mm512_sub_epi8,
     },
     hint::unreachable_unchecked,
 };
 
+#[cfg(target_arch = "aarch64")]
+use core::arch::aarch64::{
+    uint8x16_t, vandq_u8, vceqq_u8, vcltq_u8, vdup_n_u8, vdupq_n_u8, vget_lane_u64, vgetq_lane_u64,
+    vld1_u8, vld1q_u8, vld1q_u32, vmaxv_u8, vmull_p64, vmull_u8, vmulq_u32, vmvnq_u8, vorrq_u8,
+    vpaddlq_u16, vpaddlq_u32, vpaddq_u8, vreinterpret_u64_u8, vreinterpretq_u16_u8,
+    vreinterpretq_u64_u8, vshrn_n_u16, vsub_u8, vsubq_u8,
+};
+
 const ONES: u64 = 0x0101_0101_0101_0101;
 const HIGH: u64 = 0x8080_8080_8080_8080;
 const LOW: u64 = 0x7F7F_7F7F_7F7F_7F7F;
@@ -35,11 +44,19 @@ pub fn simd_u64(ptr: *const u8) -> Option<u64> {
         ssse3_u64(ptr)
     }
 
-    #[cfg(not(all(
-        target_arch = "x86_64",
-        target_feature = "ssse3",
-        feature = "simd",
-        not(feature = "runtime-detection")
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    unsafe {
+        neon_u64(ptr)
+    }
+
+    #[cfg(not(any(
+        all(
+            target_arch = "x86_64",
+            target_feature = "ssse3",
+            feature = "simd",
+            not(feature = "runtime-detection")
+        ),
+        all(target_arch = "aarch64", target_feature = "neon")
     )))]
     swar_u64(ptr)
 }
@@ -77,6 +94,25 @@ unsafe fn ssse3_u64(ptr: *const u8) -> Option<u64> {
     Some(_mm_cvtsi128_si32(t4) as u32 as _)
 }
 
+#[inline(always)]
+#[cfg(target_arch = "aarch64")]
+unsafe fn neon_u64(ptr: *const u8) -> Option<u64> {
+    let chunk = vsub_u8(vld1_u8(ptr), vdup_n_u8(b'0'));
+
+    // bytes below '0' wrap around as well
+    if vmaxv_u8(chunk) > 9 {
+        return None;
+    }
+
+    let t1 = vpaddlq_u16(vmull_u8(
+        chunk,
+        vld1_u8([10, 1, 10, 1, 10, 1, 10, 1].as_ptr()),
+    ));
+    let t2 = vpaddlq_u32(vmulq_u32(t1, vld1q_u32([100, 1, 100, 1].as_ptr())));
+
+    Some(vgetq_lane_u64(t2, 0) * 10000 + vgetq_lane_u64(t2, 1))
+}
+
 #[inline(always)]
 fn swar_u64(ptr: *const u8) -> Option<u64> {
     const ZERO: u64 = 0x3030303030303030;
@@ -115,11 +151,30 @@ unsafe fn compute_inside_mask(mask: u64) -> u64 {
 }
 
 #[inline(always)]
-#[cfg(not(all(
-    target_arch = "x86_64",
-    target_feature = "pclmulqdq",
+#[cfg(all(
+    target_arch = "aarch64",
+    target_feature = "aes",
     feature = "simd",
     not(feature = "runtime-detection")
+))]
+unsafe fn compute_inside_mask(mask: u64) -> u64 {
+    vmull_p64(mask, u64::MAX) as _
+}
+
+#[inline(always)]
+#[cfg(not(any(
+    all(
+        target_arch = "x86_64",
+        target_feature = "pclmulqdq",
+        feature = "simd",
+        not(feature = "runtime-detection")
+    ),
+    all(
+        target_arch = "aarch64",
+        target_feature = "aes",
+        feature = "simd",
+        not(feature = "runtime-detection")
+    )
 )))]
 fn compute_inside_mask(mut mask: u64) -> u64 {
     mask ^= mask << 1;
@@ -146,6 +201,42 @@ fn compute_esc_mask(mut mask: u64, last_slash: &mut u64) -> u64 {
     !((ODD ^ invert_mask) & follows_escape)
 }
 
+/// Whether NEON can be used, which is a baseline feature on most targets.
+#[inline(always)]
+#[cfg(target_arch = "aarch64")]
+fn has_neon() -> bool {
+    #[cfg(target_feature = "neon")]
+    return true;
+
+    #[cfg(all(not(target_feature = "neon"), feature = "runtime-detection"))]
+    return std::arch::is_aarch64_feature_detected!("neon");
+
+    #[cfg(not(any(target_feature = "neon", feature = "runtime-detection")))]
+    false
+}
+
+/// Narrows a comparison result into 4 bits per byte, as NEON lacks `movemask`.
+#[inline(always)]
+#[cfg(target_arch = "aarch64")]
+unsafe fn neon_nibble_mask(v: uint8x16_t) -> u64 {
+    vget_lane_u64(
+        vreinterpret_u64_u8(vshrn_n_u16(vreinterpretq_u16_u8(v), 4)),
+        0,
+    )
+}
+
+/// Packs four comparison results into a bit per byte.
+#[inline(always)]
+#[cfg(target_arch = "aarch64")]
+unsafe fn neon_bit_mask(a: uint8x16_t, b: uint8x16_t, c: uint8x16_t, d: uint8x16_t) -> u64 {
+    let bits = vld1q_u8([1, 2, 4, 8, 16, 32, 64, 128, 1, 2, 4, 8, 16, 32, 64, 128].as_ptr());
+    let ab = vpaddq_u8(vandq_u8(a, bits), vandq_u8(b, bits));
+    let cd = vpaddq_u8(vandq_u8(c, bits), vandq_u8(d, bits));
+    let tmp = vpaddq_u8(ab, cd);
+
+    vgetq_lane_u64(vreinterpretq_u64_u8(vpaddq_u8(tmp, tmp)), 0)
+}
+
 impl<'a, S: Source, C: Config> Parser<'a, S, C> {
     #[inline]
     pub(crate) fn simd_wh(&mut self) -> bool {
@@ -166,6 +257,11 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
             self.wh_sse2()
         }
 
+        #[cfg(target_arch = "aarch64")]
+        if has_neon() {
+            return unsafe { self.wh_neon() };
+        }
+
         #[cfg(not(target_arch = "x86_64"))]
         self.wh_swar()
     }
@@ -229,6 +325,35 @@ impl<'a, S: Source, C: Config> Parser<'a, S, C> {
         false
     }
 
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[target_feature(enable = "neon")]
+    unsafe fn wh_neon(&mut self) -> bool {
+        if S::NULL_PADDED || self.idx() + 16 < self.src.len() {
+            self.inc(1);
+            let chunk = vld1q_u8(self.cur_ptr());
+            let mask = neon_nibble_mask(vmvnq_u8(vorrq_u8(
+                vorrq_u8(
+                    vceqq_u8(chunk, vdupq_n_u8(b' ')),
+                    vceqq_u8(chunk, vdupq_n_u8(b'\n')),
+                ),
+                vorrq_u8(
+                    vceqq_u8(chunk, vdupq_n_u8(b'\t')),
+                    vceqq_u8(chunk, vdupq_n_u8(b'\r')),
+                ),
+            )));
+
+            if mask != 0 {
+                self.inc(mask.trailing_zeros() as usize >> 2);
+                return true;
+            }
+
+            self.inc(15)
+        }
+
+        false
+    }
+
     #[inline]
     fn wh_swar(&mut self) -> bool {
         if S::NULL_PADDED || self.idx() + 8 < self.src.len() {
@@ -364,13 +489,32 @@ impl<S: Source, C: Config> Parser<'_, S, C> {
 }
 
 impl<S: Source, C: Config> Parser<'_, S, C> {
+    // unlike `skip_container_unchecked`, these run for every string. runtime dispatch would
+    // turn them into an outlined call each, so AVX-512 is only picked at compile time.
     #[inline]
     pub(crate) fn simd_str(&mut self) -> bool {
         #[cfg(target_arch = "x86_64")]
         unsafe {
+            #[cfg(all(
+                target_feature = "avx512bw",
+                feature = "simd",
+                not(feature = "runtime-detection")
+            ))]
+            return self.str_avx512();
+
+            #[cfg(not(all(
+                target_feature = "avx512bw",
+                feature = "simd",
+                not(feature = "runtime-detection")
+            )))]
             self.str_sse2()
         }
 
+        #[cfg(target_arch = "aarch64")]
+        if has_neon() {
+            return unsafe { self.str_neon() };
+        }
+
         #[cfg(not(target_arch = "x86_64"))]
         self.str_swar()
     }
@@ -379,13 +523,120 @@ impl<S: Source, C: Config> Parser<'_, S, C> {
     pub(crate) fn simd_str_unchecked(&mut self) -> bool {
         #[cfg(target_arch = "x86_64")]
         unsafe {
+            #[cfg(all(
+                target_feature = "avx512bw",
+                feature = "simd",
+                not(feature = "runtime-detection")
+            ))]
+            return self.str_avx512_unchecked();
+
+            #[cfg(not(all(
+                target_feature = "avx512bw",
+                feature = "simd",
+                not(feature = "runtime-detection")
+            )))]
             self.str_sse2_unchecked()
         }
 
+        #[cfg(target_arch = "aarch64")]
+        if has_neon() {
+            return unsafe { self.str_neon_unchecked() };
+        }
+
         #[cfg(not(target_arch = "x86_64"))]
         self.str_swar_unchecked()
     }
 
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx512bw")]
+    unsafe fn str_avx512(&mut self) -> bool {
+        // refer to `str_sse2` for use of wrapping_add
+        if S::NULL_PADDED || likely(self.idx().wrapping_add(64) < self.src.len()) {
+            let chunk = _mm512_loadu_si512(self.cur_ptr().add(1).cast());
+            let mask = _mm512_cmpeq_epi8_mask(chunk, _mm512_set1_epi8(b'"' as _))
+                | _mm512_cmpeq_epi8_mask(chunk, _mm512_set1_epi8(b'\\' as _))
+                | _mm512_cmplt_epu8_mask(chunk, _mm512_set1_epi8(0x20));
+
+            if mask == 0 {
+                self.inc(64);
+                return true;
+            }
+
+            self.inc(mask.trailing_zeros() as _)
+        }
+
+        false
+    }
+
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx512bw")]
+    unsafe fn str_avx512_unchecked(&mut self) -> bool {
+        if S::NULL_PADDED || likely(self.idx() + 64 < self.src.len()) {
+            let chunk = _mm512_loadu_si512(self.cur_ptr().add(1).cast());
+            let mask = _mm512_cmpeq_epi8_mask(chunk, _mm512_set1_epi8(b'"' as _))
+                | _mm512_cmpeq_epi8_mask(chunk, _mm512_set1_epi8(b'\\' as _));
+
+            if mask == 0 {
+                self.inc(64);
+                return true;
+            }
+
+            self.inc(mask.trailing_zeros() as _)
+        }
+
+        false
+    }
+
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[target_feature(enable = "neon")]
+    unsafe fn str_neon(&mut self) -> bool {
+        // refer to `str_sse2` for use of wrapping_add
+        if S::NULL_PADDED || likely(self.idx().wrapping_add(16) < self.src.len()) {
+            let chunk = vld1q_u8(self.cur_ptr().add(1));
+            let mask = neon_nibble_mask(vorrq_u8(
+                vorrq_u8(
+                    vceqq_u8(chunk, vdupq_n_u8(b'"')),
+                    vceqq_u8(chunk, vdupq_n_u8(b'\\')),
+                ),
+                vcltq_u8(chunk, vdupq_n_u8(0x20)),
+            ));
+
+            if mask == 0 {
+                self.inc(16);
+                return true;
+            }
+
+            self.inc(mask.trailing_zeros() as usize >> 2)
+        }
+
+        false
+    }
+
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[target_feature(enable = "neon")]
+    unsafe fn str_neon_unchecked(&mut self) -> bool {
+        if S::NULL_PADDED || likely(self.idx() + 16 < self.src.len()) {
+            let chunk = vld1q_u8(self.cur_ptr().add(1));
+            let mask = neon_nibble_mask(vorrq_u8(
+                vceqq_u8(chunk, vdupq_n_u8(b'"')),
+                vceqq_u8(chunk, vdupq_n_u8(b'\\')),
+            ));
+
+            if mask == 0 {
+                self.inc(16);
+                return true;
+            }
+
+            self.inc(mask.trailing_zeros() as usize >> 2)
+        }
+
+        false
+    }
+
     #[inline]
     #[cfg(target_arch = "x86_64")]
     #[target_feature(enable = "sse2")]
@@ -514,10 +765,13 @@ impl<S: Source, C: Config> Parser<'_, S, C> {
                 static FLAG: AtomicU8 = AtomicU8::new(0);
 
                 match FLAG.load(Relaxed) {
+                    3 => self.skip_container_avx512(),
                     2 => self.skip_container_avx2(),
                     1 => self.skip_container_sse2(),
                     0 => {
-                        let tmp = if is_x86_feature_detected!("avx2") {
+                        let tmp = if is_x86_feature_detected!("avx512bw") {
+                            3
+                        } else if is_x86_feature_detected!("avx2") {
                             2
                         } else {
                             1
@@ -525,6 +779,7 @@ impl<S: Source, C: Config> Parser<'_, S, C> {
 
                         FLAG.store(tmp, Relaxed);
                         match tmp {
+                            3 => self.skip_container_avx512(),
                             2 => self.skip_container_avx2(),
                             1 => self.skip_container_sse2(),
                             _ => unreachable_unchecked(),
@@ -536,7 +791,14 @@ impl<S: Source, C: Config> Parser<'_, S, C> {
 
             #[cfg(not(feature = "runtime-detection"))]
             {
-                #[cfg(all(feature = "simd", target_feature = "avx2"))]
+                #[cfg(all(feature = "simd", target_feature = "avx512bw"))]
+                return self.skip_container_avx512();
+
+                #[cfg(all(
+                    feature = "simd",
+                    target_feature = "avx2",
+                    not(target_feature = "avx512bw")
+                ))]
                 return self.skip_container_avx2();
 
                 #[cfg(not(all(feature = "simd", target_feature = "avx2")))]
@@ -544,10 +806,80 @@ impl<S: Source, C: Config> Parser<'_, S, C> {
             }
         }
 
+        #[cfg(target_arch = "aarch64")]
+        if has_neon() {
+            return self.skip_container_neon();
+        }
+
         #[cfg(not(target_arch = "x86_64"))]
         self.skip_container_naive()
     }
 
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx512bw")]
+    unsafe fn skip_container_avx512(&mut self) {
+        let quote = _mm512_set1_epi8(b'"' as _);
+        let slash = _mm512_set1_epi8(b'\\' as _);
+        let mut tail = [0u8; 64];
+        let mut depth = 0_usize;
+        let mut in_string = 0;
+        let mut last_slash = 0;
+
+        loop {
+            let ptr = if self.idx() + 64 < self.src.len() {
+                self.cur_ptr()
+            } else {
+                let tmp = tail.as_mut_ptr();
+                tmp.copy_from_nonoverlapping(self.cur_ptr(), self.src.len() - self.idx());
+                tmp
+            };
+
+            let chunk = _mm512_loadu_si512(ptr.cast());
+            let mut quote_mask = _mm512_cmpeq_epi8_mask(chunk, quote) & !last_slash;
+            let mut slash_mask = _mm512_cmpeq_epi8_mask(chunk, slash);
+
+            if slash_mask != 0 {
+                let mask = slash_mask;
+                slash_mask = last_slash;
+                quote_mask &= compute_esc_mask(mask, &mut slash_mask);
+            }
+
+            let inside_mask = compute_inside_mask(quote_mask ^ in_string);
+
+            // check for '{', '}', '[', ']'
+            let sp_mask = _mm512_cmpeq_epi8_mask(
+                _mm512_and_si512(
+                    _mm512_sub_epi8(chunk, _mm512_set1_epi8(91)),
+                    _mm512_set1_epi8(-35),
+                ),
+                _mm512_setzero_si512(),
+            );
+
+            let mut mask = sp_mask & !inside_mask;
+
+            in_string = inside_mask >> 63;
+            last_slash = slash_mask;
+
+            while mask != 0 {
+                let idx = mask.trailing_zeros() as _;
+                match *self.cur_ptr().add(idx) {
+                    b'{' | b'[' => depth += 1,
+                    _ => {
+                        depth -= 1;
+                        if depth == 0 {
+                            _mm256_zeroupper();
+                            return self.inc(idx);
+                        }
+                    }
+                }
+                mask &= mask - 1;
+            }
+
+            self.inc(64)
+        }
+    }
+
     #[inline]
     #[cfg(target_arch = "x86_64")]
     #[target_feature(enable = "avx2")]
@@ -715,6 +1047,85 @@ impl<S: Source, C: Config> Parser<'_, S, C> {
         }
     }
 
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[target_feature(enable = "neon")]
+    unsafe fn skip_container_neon(&mut self) {
+        let quote = vdupq_n_u8(b'"');
+        let slash = vdupq_n_u8(b'\\');
+        let mut tail = [0u8; 64];
+        let mut depth = 0_usize;
+        let mut in_string = 0;
+        let mut last_slash = 0;
+
+        loop {
+            let ptr = if self.idx() + 64 < self.src.len() {
+                self.cur_ptr()
+            } else {
+                let tmp = tail.as_mut_ptr();
+                tmp.copy_from_nonoverlapping(self.cur_ptr(), self.src.len() - self.idx());
+                tmp
+            };
+
+            let c0 = vld1q_u8(ptr);
+            let c1 = vld1q_u8(ptr.add(16));
+            let c2 = vld1q_u8(ptr.add(32));
+            let c3 = vld1q_u8(ptr.add(48));
+
+            let mut quote_mask = neon_bit_mask(
+                vceqq_u8(c0, quote),
+                vceqq_u8(c1, quote),
+                vceqq_u8(c2, quote),
+                vceqq_u8(c3, quote),
+            ) & !last_slash;
+
+            let mut slash_mask = neon_bit_mask(
+                vceqq_u8(c0, slash),
+                vceqq_u8(c1, slash),
+                vceqq_u8(c2, slash),
+                vceqq_u8(c3, slash),
+            );
+
+            if slash_mask != 0 {
+                let mask = slash_mask;
+                slash_mask = last_slash;
+                quote_mask &= compute_esc_mask(mask, &mut slash_mask);
+            }
+
+            let inside_mask = compute_inside_mask(quote_mask ^ in_string);
+
+            // check for '{', '}', '[', ']'
+            let sp = |c| {
+                vceqq_u8(
+                    vandq_u8(vsubq_u8(c, vdupq_n_u8(91)), vdupq_n_u8(0xDD)),
+                    vdupq_n_u8(0),
+                )
+            };
+            let sp_mask = neon_bit_mask(sp(c0), sp(c1), sp(c2), sp(c3));
+
+            let mut mask = sp_mask & !inside_mask;
+
+            in_string = inside_mask >> 63;
+            last_slash = slash_mask;
+
+            while mask != 0 {
+                let idx = mask.trailing_zeros() as _;
+                match *self.cur_ptr().add(idx) {
+                    b'{' | b'[' => depth += 1,
+                    _ => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return self.inc(idx);
+                        }
+                    }
+                }
+                mask &= mask - 1;
+            }
+
+            self.inc(64)
+        }
+    }
+
     #[inline]
     unsafe fn skip_container_naive(&mut self) {
         let mut depth = 0_usize;
@@ -866,3 +1277,418 @@ impl<S: Source, C: Config> Parser<'_, S, C> {
         }
     }
 }
+
+/// Calls `f` with the offset of every structural character in order. That is brackets,
+/// commas and colons outside strings along with the opening quotes of strings.
+///
+/// The source must not end inside a string, otherwise the result is unspecified.
+pub(crate) fn for_each_structural(src: &[u8], mut f: impl FnMut(usize)) {
+    let mut tail = [0u8; 64];
+    let mut in_string = 0;
+    let mut last_slash = 0;
+    let mut base = 0;
+
+    while base < src.len() {
+        let ptr = match base + 64 <= src.len() {
+            true => unsafe { src.as_ptr().add(base) },
+            _ => {
+                tail[..src.len() - base].copy_from_slice(&src[base..]);
+                tail.as_ptr()
+            }
+        };
+
+        let [quote, mut slash_mask, op] = unsafe { block_masks(ptr) };
+        let mut quote_mask = quote & !last_slash;
+
+        if slash_mask != 0 {
+            let mask = slash_mask;
+            slash_mask = last_slash;
+            quote_mask &= compute_esc_mask(mask, &mut slash_mask);
+        }
+
+        let inside_mask = unsafe { compute_inside_mask(quote_mask ^ in_string) };
+        let mut mask = op & !inside_mask | quote_mask & inside_mask;
+
+        in_string = inside_mask >> 63;
+        last_slash = slash_mask;
+
+        while mask != 0 {
+            f(base + mask.trailing_zeros() as usize);
+            mask &= mask - 1;
+        }
+
+        base += 64;
+    }
+}
+
+/// Returns the masks of quotes, backslashes and `{}[],:` in the given 64 bytes.
+#[inline(always)]
+unsafe fn block_masks(ptr: *const u8) -> [u64; 3] {
+    #[cfg(target_arch = "x86_64")]
+    return block_masks_sse2(ptr);
+
+    #[cfg(target_arch = "aarch64")]
+    if has_neon() {
+        return block_masks_neon(ptr);
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    block_masks_naive(ptr)
+}
+
+#[inline]
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn block_masks_sse2(ptr: *const u8) -> [u64; 3] {
+    let mut masks = [0; 3];
+
+    for i in 0..4 {
+        let chunk = _mm_loadu_si128(ptr.add(i * 16).cast());
+        let quote = _mm_cmpeq_epi8(chunk, _mm_set1_epi8(b'"' as _));
+        let slash = _mm_cmpeq_epi8(chunk, _mm_set1_epi8(b'\\' as _));
+        let op = _mm_or_si128(
+            // '{', '}', '[', ']'
+            _mm_cmpeq_epi8(
+                _mm_and_si128(_mm_sub_epi8(chunk, _mm_set1_epi8(91)), _mm_set1_epi8(-35)),
+                _mm_setzero_si128(),
+            ),
+            _mm_or_si128(
+                _mm_cmpeq_epi8(chunk, _mm_set1_epi8(b',' as _)),
+                _mm_cmpeq_epi8(chunk, _mm_set1_epi8(b':' as _)),
+            ),
+        );
+
+        for (mask, v) in masks.iter_mut().zip([quote, slash, op]) {
+            *mask |= (_mm_movemask_epi8(v) as u16 as u64) << (i * 16);
+        }
+    }
+
+    masks
+}
+
+#[inline]
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn block_masks_neon(ptr: *const u8) -> [u64; 3] {
+    let c = [
+        vld1q_u8(ptr),
+        vld1q_u8(ptr.add(16)),
+        vld1q_u8(ptr.add(32)),
+        vld1q_u8(ptr.add(48)),
+    ];
+    let quote = c.map(|v| vceqq_u8(v, vdupq_n_u8(b'"')));
+    let slash = c.map(|v| vceqq_u8(v, vdupq_n_u8(b'\\')));
+    let op = c.map(|v| {
+        vorrq_u8(
+            // '{', '}', '[', ']'
+            vceqq_u8(
+                vandq_u8(vsubq_u8(v, vdupq_n_u8(91)), vdupq_n_u8(0xDD)),
+                vdupq_n_u8(0),
+            ),
+            vorrq_u8(vceqq_u8(v, vdupq_n_u8(b',')), vceqq_u8(v, vdupq_n_u8(b':'))),
+        )
+    });
+
+    [quote, slash, op].map(|[a, b, c, d]| neon_bit_mask(a, b, c, d))
+}
+
+#[inline]
+unsafe fn block_masks_naive(ptr: *const u8) -> [u64; 3] {
+    let mut masks = [0; 3];
+
+    for i in 0..64 {
+        match *ptr.add(i) {
+            b'"' => masks[0] |= 1 << i,
+            b'\\' => masks[1] |= 1 << i,
+            b'{' | b'}' | b'[' | b']' | b',' | b':' => masks[2] |= 1 << i,
+            _ => {}
+        }
+    }
+
+    masks
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use std::{string::String, vec::Vec};
+
+    type P<'a> = Parser<'a, &'a str>;
+
+    // xorshift, good enough to pick bytes.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 as usize
+        }
+
+        fn pick<'a>(&mut self, v: &[&'a str]) -> &'a str {
+            v[self.next() % v.len()]
+        }
+    }
+
+    fn inputs(alphabet: &[&str]) -> Vec<String> {
+        let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+        (0..200)
+            .map(|i| (0..i).map(|_| rng.pick(alphabet)).collect())
+            .collect()
+    }
+
+    fn parser_at(src: &str, idx: usize) -> P<'_> {
+        let mut p = Parser::new(src);
+        p.inc(idx.wrapping_add(1));
+        p
+    }
+
+    fn skip_wh(src: &str, idx: usize, f: impl Fn(&mut P) -> bool) -> usize {
+        let mut p = parser_at(src, idx);
+        let bytes = src.as_bytes();
+
+        loop {
+            let tmp = p.idx();
+            if f(&mut p) {
+                return p.idx();
+            }
+
+            if tmp == p.idx() {
+                break;
+            }
+        }
+
+        while p.idx() + 1 < bytes.len() {
+            p.inc(1);
+            if !matches!(p.cur(), b' ' | b'\n' | b'\t' | b'\r') {
+                return p.idx();
+            }
+        }
+
+        bytes.len()
+    }
+
+    fn skip_str(src: &str, idx: usize, checked: bool, f: impl Fn(&mut P) -> bool) -> usize {
+        let mut p = parser_at(src, idx);
+        let bytes = src.as_bytes();
+        while f(&mut p) {}
+
+        while p.idx().wrapping_add(1) < bytes.len() {
+            match bytes[p.idx().wrapping_add(1)] {
+                b'"' | b'\\' => break,
+                ..0x20 if checked => break,
+                _ => p.inc(1),
+            }
+        }
+
+        p.idx()
+    }
+
+    fn container(rng: &mut Rng, depth: usize, out: &mut String) {
+        let (open, close) = match rng.next() % 2 {
+            0 => ('[', ']'),
+            _ => ('{', '}'),
+        };
+
+        out.push(open);
+        for _ in 0..rng.next() % 6 {
+            match rng.next() % 4 {
+                0 if depth < 8 => container(rng, depth + 1, out),
+                1 => {
+                    out.push('"');
+                    for _ in 0..rng.next() % 40 {
+                        out.push_str(rng.pick(&["a", "\\\\", "\\\"", "[", "}", " ", "é", "{"]));
+                    }
+                    out.push('"');
+                }
+                _ => out.push_str(rng.pick(&["1", "true", " ", ",", ":", "\n"])),
+            }
+        }
+        out.push(close);
+    }
+
+    #[test]
+    fn whitespace() {
+        for src in inputs(&[" ", "\n", "\t", "\r", " ", "  ", "a", "\"", "é"]) {
+            for idx in 0..src.len() {
+                let expected = skip_wh(&src, idx, |_| false);
+
+                assert_eq!(skip_wh(&src, idx, |p| p.wh_swar()), expected);
+
+                #[cfg(target_arch = "x86_64")]
+                {
+                    assert_eq!(skip_wh(&src, idx, |p| unsafe { p.wh_sse2() }), expected);
+
+                    if is_x86_feature_detected!("sse4.2") {
+                        let res = skip_wh(&src, idx, |p| unsafe { p.wh_sse4_2() });
+                        assert_eq!(res, expected);
+                    }
+                }
+
+                #[cfg(target_arch = "aarch64")]
+                if has_neon() {
+                    assert_eq!(skip_wh(&src, idx, |p| unsafe { p.wh_neon() }), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn string() {
+        for src in inputs(&["a", "bc", "\"", "\\", "\n", "\u{1}", "é", " ", "defgh"]) {
+            for idx in [usize::MAX].into_iter().chain(0..src.len()) {
+                for checked in [true, false] {
+                    // only the checked ones are used when serializing, starting from usize::MAX
+                    if !checked && idx == usize::MAX {
+                        continue;
+                    }
+
+                    let expected = skip_str(&src, idx, checked, |_| false);
+                    let res = skip_str(&src, idx, checked, |p| match checked {
+                        true => p.str_swar(),
+                        _ => p.str_swar_unchecked(),
+                    });
+                    assert_eq!(res, expected);
+
+                    #[cfg(target_arch = "x86_64")]
+                    {
+                        let res = skip_str(&src, idx, checked, |p| unsafe {
+                            match checked {
+                                true => p.str_sse2(),
+                                _ => p.str_sse2_unchecked(),
+                            }
+                        });
+                        assert_eq!(res, expected);
+
+                        if is_x86_feature_detected!("avx512bw") {
+                            let res = skip_str(&src, idx, checked, |p| unsafe {
+                                match checked {
+                                    true => p.str_avx512(),
+                                    _ => p.str_avx512_unchecked(),
+                                }
+                            });
+                            assert_eq!(res, expected);
+                        }
+                    }
+
+                    #[cfg(target_arch = "aarch64")]
+                    if has_neon() {
+                        let res = skip_str(&src, idx, checked, |p| unsafe {
+                            match checked {
+                                true => p.str_neon(),
+                                _ => p.str_neon_unchecked(),
+                            }
+                        });
+                        assert_eq!(res, expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn skip_container() {
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+
+        for _ in 0..500 {
+            let mut src = String::new();
+            container(&mut rng, 0, &mut src);
+            let len = src.len();
+            src.push_str(rng.pick(&["", " ]", "}}}}", "[[[[", " \"\\\""]));
+
+            let run = |f: unsafe fn(&mut P)| {
+                let mut p = parser_at(&src, 0);
+                unsafe { f(&mut p) };
+                p.idx()
+            };
+
+            assert_eq!(run(|p| unsafe { p.skip_container_naive() }), len - 1);
+
+            #[cfg(target_arch = "x86_64")]
+            {
+                assert_eq!(run(|p| unsafe { p.skip_container_sse2() }), len - 1);
+
+                if is_x86_feature_detected!("avx2") {
+                    assert_eq!(run(|p| unsafe { p.skip_container_avx2() }), len - 1);
+                }
+
+                if is_x86_feature_detected!("avx512bw") {
+                    assert_eq!(run(|p| unsafe { p.skip_container_avx512() }), len - 1);
+                }
+            }
+
+            #[cfg(target_arch = "aarch64")]
+            if has_neon() {
+                assert_eq!(run(|p| unsafe { p.skip_container_neon() }), len - 1);
+            }
+        }
+    }
+
+    #[test]
+    fn structural() {
+        for src in inputs(&[
+            "a", "\"", "\\", "{", "}", "[", "]", ",", ":", " ", "bcdefgh",
+        ]) {
+            let mut expected = Vec::new();
+            let (mut inside, mut escaped) = (false, false);
+
+            // like the escape mask, backslashes only matter for the quote after them.
+            for (i, &c) in src.as_bytes().iter().enumerate() {
+                match c {
+                    b'\\' if !escaped => {
+                        escaped = true;
+                        continue;
+                    }
+                    b'"' if !escaped => {
+                        if !inside {
+                            expected.push(i);
+                        }
+
+                        inside = !inside;
+                    }
+                    b'{' | b'}' | b'[' | b']' | b',' | b':' if !inside => expected.push(i),
+                    _ => {}
+                }
+
+                escaped = false;
+            }
+
+            let mut found = Vec::new();
+            for_each_structural(src.as_bytes(), |v| found.push(v));
+            assert_eq!(found, expected, "{src:?}");
+
+            for block in src.as_bytes().chunks_exact(64) {
+                let expected = unsafe { block_masks_naive(block.as_ptr()) };
+                assert_eq!(unsafe { block_masks(block.as_ptr()) }, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn digits() {
+        let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+        let alphabet = b"01234567890123456789/:a .";
+
+        for _ in 0..10000 {
+            let buf: [u8; 8] = core::array::from_fn(|_| match rng.next() % 8 {
+                0 => alphabet[rng.next() % alphabet.len()],
+                _ => b'0' + (rng.next() % 10) as u8,
+            });
+            let expected = match buf.iter().all(u8::is_ascii_digit) {
+                true => Some(buf.iter().fold(0, |acc, v| acc * 10 + (v - b'0') as u64)),
+                _ => None,
+            };
+
+            assert_eq!(swar_u64(buf.as_ptr()), expected);
+
+            #[cfg(target_arch = "x86_64")]
+            if is_x86_feature_detected!("ssse3") {
+                assert_eq!(unsafe { ssse3_u64(buf.as_ptr()) }, expected);
+            }
+
+            #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+            assert_eq!(unsafe { neon_u64(buf.as_ptr()) }, expected);
+        }
+    }
+}
@@ -70,6 +70,27 @@ pub trait Source {
     /// The parser requires at least 64 bytes of data to be available during parsing,
     /// otherwise it may give false EOF-related errors.
     fn len(&mut self) -> usize;
+
+    /// Takes the I/O error that occurred while loading more data, if any.
+    ///
+    /// Sources that fail to load more data should behave as if they reached EOF and
+    /// report the cause here. The parser calls this once parsing fails, so the error
+    /// is surfaced instead of an EOF-related one.
+    #[inline(always)]
+    #[cfg(feature = "std")]
+    fn take_error(&mut self) -> Option<std::io::Error> {
+        None
+    }
+
+    /// Returns the structural index of the source, if it has one.
+    ///
+    /// The parser jumps through it instead of scanning when skipping to a path.
+    #[doc(hidden)]
+    #[inline(always)]
+    #[cfg(feature = "alloc")]
+    fn index(&self) -> Option<&crate::index::StructuralIndex<'_>> {
+        None
+    }
 }
 
 /// Marker trait indicating whether a source's data is stable or not.
@@ -4,7 +4,7 @@ use crate::{
 };
 use alloc::alloc::{alloc, dealloc, handle_alloc_error, realloc};
 use core::{alloc::Layout, ptr::dangling_mut, slice::from_raw_parts_mut};
-use std::io::Read;
+use std::io::{Error, ErrorKind, Read};
 
 /// A minimal buffered wrapper for types implementing [`Read`].
 pub struct Reader<const UTF8: bool, R> {
@@ -14,6 +14,9 @@ pub struct Reader<const UTF8: bool, R> {
     cap: usize,
     recent: usize,
     offset: usize,
+    // data before this offset is no longer needed.
+    keep: usize,
+    err: Option<Error>,
 }
 
 impl<R> Reader<false, R> {
@@ -27,6 +30,8 @@ impl<R> Reader<false, R> {
             cap: 0,
             recent: 0,
             offset: 0,
+            keep: 0,
+            err: None,
         }
     }
 }
@@ -42,10 +47,25 @@ impl<R> Reader<true, R> {
             cap: 0,
             recent: 0,
             offset: 0,
+            keep: 0,
+            err: None,
         }
     }
 }
 
+impl<const UTF8: bool, R> Reader<UTF8, R> {
+    #[inline(never)]
+    unsafe fn shift(&mut self) {
+        let old = self.keep - self.offset;
+        let remaining = self.len - old;
+
+        self.buf.add(old).copy_to(self.buf, remaining);
+        self.offset = self.keep;
+        self.len = remaining;
+        self.recent = self.recent.max(self.keep);
+    }
+}
+
 impl<const UTF8: bool, R: Read> Source for Reader<UTF8, R> {
     const UTF8: bool = UTF8;
     const INSITU: bool = false;
@@ -63,29 +83,26 @@ impl<const UTF8: bool, R: Read> Source for Reader<UTF8, R> {
     }
 
     fn trim(&mut self, until: usize) {
-        #[inline(never)]
-        unsafe fn shift<const V: bool>(
-            this: &mut Reader<V, impl Read>,
-            remaining: usize,
-            until: usize,
-        ) {
-            let old = this.len - remaining;
-
-            this.buf.add(old).copy_to(this.buf, remaining);
-            this.offset += old;
-            this.len = remaining;
-            this.recent = until;
-        }
+        self.keep = until;
 
+        // the rest gets discarded lazily before loading more data.
         let remaining = self.len - (until - self.offset);
         if remaining <= 128 {
-            unsafe { shift(self, remaining, until) }
+            unsafe { self.shift() }
         }
     }
 
     fn len(&mut self) -> usize {
         #[inline(never)]
         unsafe fn load<const V: bool>(this: &mut Reader<V, impl Read>) {
+            if this.err.is_some() {
+                return;
+            }
+
+            if this.keep != this.offset {
+                this.shift();
+            }
+
             if this.cap - this.len < 1024 {
                 let layout;
                 let new_cap;
@@ -114,13 +131,18 @@ impl<const UTF8: bool, R: Read> Source for Reader<UTF8, R> {
                 this.cap = new_cap;
             }
 
-            this.len += this
-                .reader
-                .read(from_raw_parts_mut(
+            loop {
+                match this.reader.read(from_raw_parts_mut(
                     this.buf.add(this.len),
                     this.cap - this.len,
-                ))
-                .unwrap();
+                )) {
+                    Ok(v) => break this.len += v,
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    // acts as if it reached EOF from here on. the parser will
+                    // pick the error up with `take_error` once it fails.
+                    Err(e) => break this.err = Some(e),
+                }
+            }
         }
 
         if self.len - (self.recent - self.offset) < 64 {
@@ -129,6 +151,11 @@ impl<const UTF8: bool, R: Read> Source for Reader<UTF8, R> {
 
         self.len + self.offset
     }
+
+    #[inline]
+    fn take_error(&mut self) -> Option<Error> {
+        self.err.take()
+    }
 }
 
 impl<const UTF8: bool, R> Drop for Reader<UTF8, R> {
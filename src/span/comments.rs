@@ -0,0 +1,191 @@
+use super::{GenericValue, LineIndex, Span};
+use crate::Comment;
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{fmt::Write, ops::Deref};
+
+/// Comments attached to the object members and array elements they document, keyed by JSON pointer.
+///
+/// A comment is attached as trailing to the value ending right before it on the same line,
+/// otherwise as leading to the value starting right after it in the same container. The rest,
+/// e.g. comments right before a closing bracket, are kept as detached. Object members span
+/// from their key, and the root value is keyed by an empty pointer.
+///
+/// # Example
+/// ```
+/// use flexon::{Parser, config::CTConfig, span::{CommentMap, LineIndex, Value}};
+///
+/// let src = r#"{
+///     // port to listen on
+///     "port": 8080, // required
+///     "hosts": ["a" /* primary */, "b"]
+/// }"#;
+///
+/// let mut parser = Parser::new_with(src, CTConfig::new().allow_comments());
+/// let val: Value = parser.parse()?;
+/// let map = CommentMap::new(&val, parser.take_comments(), &LineIndex::new(src));
+/// let port = map.get("/port").unwrap();
+///
+/// assert_eq!(port.leading()[0].as_str(), " port to listen on");
+/// assert_eq!(port.trailing()[0].as_str(), " required");
+/// assert_eq!(map.get("/hosts/0").unwrap().trailing()[0].as_str(), " primary ");
+/// # Ok::<(), flexon::span::Span<flexon::Error>>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct CommentMap<'a> {
+    map: BTreeMap<String, Attached<'a>>,
+    detached: Vec<Comment<'a>>,
+}
+
+/// Comments attached to a single value.
+#[derive(Debug, Default)]
+pub struct Attached<'a> {
+    leading: Vec<Comment<'a>>,
+    trailing: Vec<Comment<'a>>,
+}
+
+struct Target {
+    path: String,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> CommentMap<'a> {
+    /// Attaches the given comments to the values of `val`, which must be parsed from the same
+    /// source as the comments. `index` is used to tell whether a comment follows a value on the same line.
+    pub fn new<S: Deref<Target = str>>(
+        val: &Span<GenericValue<S>>,
+        comments: Vec<Comment<'a>>,
+        index: &LineIndex,
+    ) -> Self {
+        let mut targets = Vec::new();
+        collect(val, &mut String::new(), val.start(), &mut targets);
+
+        // targets are in document order, so they are sorted by start already.
+        let mut ends: Vec<_> = targets
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (v.end, i))
+            .collect();
+        ends.sort_unstable();
+
+        let mut slots: Vec<Attached> = targets.iter().map(|_| Attached::default()).collect();
+        let mut detached = Vec::new();
+
+        for c in comments {
+            let (start, end) = (c.start(), c.end());
+
+            // value ending right before the comment, with nothing else starting in between.
+            let i = ends.partition_point(|v| v.0 < start);
+            if i != 0 {
+                let (e, t) = ends[i - 1];
+                if index.line_col(e).line() == index.line_col(start).line()
+                    && targets.partition_point(|v| v.start <= e)
+                        == targets.partition_point(|v| v.start < start)
+                {
+                    slots[t].trailing.push(c);
+                    continue;
+                }
+            }
+
+            // value starting right after the comment, with no container closing in between.
+            let j = targets.partition_point(|v| v.start <= end);
+            if j != targets.len()
+                && ends.partition_point(|v| v.0 <= end)
+                    == ends.partition_point(|v| v.0 < targets[j].start)
+            {
+                slots[j].leading.push(c);
+                continue;
+            }
+
+            detached.push(c);
+        }
+
+        Self {
+            map: targets
+                .into_iter()
+                .zip(slots)
+                .filter(|(_, v)| !v.is_empty())
+                .map(|(t, v)| (t.path, v))
+                .collect(),
+            detached,
+        }
+    }
+
+    /// Returns the comments attached to the value at the given JSON pointer.
+    #[inline]
+    pub fn get(&self, pointer: &str) -> Option<&Attached<'a>> {
+        self.map.get(pointer)
+    }
+
+    /// Returns an iterator over the JSON pointers and their comments, ordered by the pointer.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Attached<'a>)> {
+        self.map.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Returns the comments that could not be attached to any value.
+    #[inline]
+    pub fn detached(&self) -> &[Comment<'a>] {
+        &self.detached
+    }
+}
+
+impl<'a> Attached<'a> {
+    /// Returns the comments preceding the value.
+    #[inline]
+    pub fn leading(&self) -> &[Comment<'a>] {
+        &self.leading
+    }
+
+    /// Returns the comments following the value on the same line.
+    #[inline]
+    pub fn trailing(&self) -> &[Comment<'a>] {
+        &self.trailing
+    }
+
+    /// Returns `true` if there are no comments attached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.leading.is_empty() && self.trailing.is_empty()
+    }
+}
+
+fn collect<S: Deref<Target = str>>(
+    val: &Span<GenericValue<S>>,
+    path: &mut String,
+    start: usize,
+    out: &mut Vec<Target>,
+) {
+    out.push(Target {
+        path: path.clone(),
+        start,
+        end: val.end(),
+    });
+
+    let len = path.len();
+    match val.data() {
+        GenericValue::Array(arr) => {
+            for (i, v) in arr.iter().enumerate() {
+                _ = write!(path, "/{i}");
+                collect(v, path, v.start(), out);
+                path.truncate(len);
+            }
+        }
+        GenericValue::Object(obj) => {
+            for (k, v) in obj.as_slice() {
+                path.push('/');
+                for c in k.data().chars() {
+                    match c {
+                        '~' => path.push_str("~0"),
+                        '/' => path.push_str("~1"),
+                        _ => path.push(c),
+                    }
+                }
+
+                collect(v, path, k.start(), out);
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
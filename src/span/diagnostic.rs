@@ -0,0 +1,230 @@
+use super::{LineIndex, Span};
+use crate::{Error, ErrorKind};
+use core::fmt::{self, Display, Formatter};
+
+/// Errors that can be rendered by [`Diagnostic`].
+pub trait Diagnose {
+    /// Returns the starting and ending byte offset of the error.
+    fn span(&self) -> [usize; 2];
+
+    /// Writes the error message.
+    fn message(&self, f: &mut Formatter<'_>) -> fmt::Result;
+
+    /// Returns a hint on how to resolve the error, if there is any.
+    fn hint(&self) -> Option<&'static str>;
+}
+
+/// Renders an error along with the offending part of the source, similar to rustc.
+///
+/// # Example
+/// ```
+/// use flexon::{Parser, span::{Diagnostic, Value}};
+///
+/// let src = "{\n  \"a\": 1,}";
+/// let err = Parser::from_str(src).parse::<Value>().unwrap_err();
+/// let msg = Diagnostic::new(src, &err).with_name("config.json").to_string();
+///
+/// assert_eq!(
+///     msg,
+///     r#"error: trailing comma
+///  --> config.json:2:9
+///   |
+/// 2 |   "a": 1,}
+///   |         ^
+///   |
+///   = help: did you mean to allow trailing commas?"#
+/// );
+/// ```
+pub struct Diagnostic<'a, E> {
+    src: &'a str,
+    err: &'a E,
+    name: Option<&'a str>,
+    index: Option<&'a LineIndex>,
+}
+
+impl<'a, E: Diagnose> Diagnostic<'a, E> {
+    /// Creates a diagnostic of the given error and the source it originated from.
+    #[inline]
+    pub fn new(src: &'a str, err: &'a E) -> Self {
+        Self {
+            src,
+            err,
+            name: None,
+            index: None,
+        }
+    }
+
+    /// Sets the name of the source, e.g. the file path.
+    #[inline]
+    pub fn with_name(mut self, name: &'a str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Uses the given line index of the source rather than building one on every render.
+    ///
+    /// Useful when rendering many errors of the same source.
+    #[inline]
+    pub fn with_index(mut self, index: &'a LineIndex) -> Self {
+        self.index = Some(index);
+        self
+    }
+}
+
+impl<E: Diagnose> Display for Diagnostic<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let src = self.src;
+        let tmp;
+        let index = match self.index {
+            Some(v) => v,
+            _ => {
+                tmp = LineIndex::new(src);
+                &tmp
+            }
+        };
+
+        let [start, end] = self.err.span();
+        let start = floor_char_boundary(src, start.min(src.len()));
+        // spans are inclusive
+        let end = ceil_char_boundary(src, end.max(start).saturating_add(1).min(src.len()));
+        let pos = index.line_col_chars(start);
+        let (line_no, col) = (pos.line(), pos.column());
+        let range = index.line_range(line_no);
+        let line_start = range.start;
+        let line = src[range].trim_end_matches('\r');
+        let width = match end.min(line_start + line.len()) {
+            v if v > start => src[start..v].chars().count(),
+            _ => 1,
+        };
+        let pad = line_no.ilog10() as usize + 1;
+
+        f.write_str("error: ")?;
+        self.err.message(f)?;
+        write!(f, "\n{:pad$}--> ", "")?;
+        if let Some(name) = self.name {
+            write!(f, "{name}:")?;
+        }
+
+        write!(
+            f,
+            "{line_no}:{col}\n{:pad$} |\n{line_no} | {line}\n{:pad$} | ",
+            "", ""
+        )?;
+        for c in src[line_start..start].chars() {
+            f.write_str(if c == '\t' { "\t" } else { " " })?;
+        }
+
+        for _ in 0..width {
+            f.write_str("^")?;
+        }
+
+        if let Some(hint) = self.err.hint() {
+            write!(f, "\n{:pad$} |\n{:pad$} = help: {hint}", "", "")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Diagnose for Span<Error> {
+    #[inline]
+    fn span(&self) -> [usize; 2] {
+        [self.start, self.end]
+    }
+
+    #[inline]
+    fn message(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.data, f)
+    }
+
+    #[inline]
+    fn hint(&self) -> Option<&'static str> {
+        hint(self.data.kind())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Diagnose for crate::serde::de::Error {
+    #[inline]
+    fn span(&self) -> [usize; 2] {
+        [self.start(), self.end()]
+    }
+
+    #[inline]
+    fn message(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+
+    fn hint(&self) -> Option<&'static str> {
+        use crate::serde::de::Kind;
+
+        hint(&match self.kind() {
+            Kind::Eof => ErrorKind::Eof,
+            Kind::ExpectedColon => ErrorKind::ExpectedColon,
+            Kind::UnexpectedToken => ErrorKind::UnexpectedToken,
+            Kind::UnclosedString => ErrorKind::UnclosedString,
+            Kind::ControlCharacter => ErrorKind::ControlCharacter,
+            Kind::InvalidEscapeSequnce => ErrorKind::InvalidEscapeSequnce,
+            Kind::InvalidLiteral => ErrorKind::InvalidLiteral,
+            Kind::TrailingComma => ErrorKind::TrailingComma,
+            Kind::LeadingDecimal => ErrorKind::LeadingDecimal,
+            Kind::TrailingDecimal => ErrorKind::TrailingDecimal,
+            Kind::LeadingZero => ErrorKind::LeadingZero,
+            Kind::NumberOverflow => ErrorKind::NumberOverflow,
+            Kind::DepthLimitExceeded => ErrorKind::DepthLimitExceeded,
+            Kind::StringTooLong => ErrorKind::StringTooLong,
+            Kind::ContainerTooLarge => ErrorKind::ContainerTooLarge,
+            Kind::AllocLimitExceeded => ErrorKind::AllocLimitExceeded,
+            Kind::DuplicateKey => ErrorKind::DuplicateKey,
+            _ => return None,
+        })
+    }
+}
+
+fn floor_char_boundary(s: &str, mut idx: usize) -> usize {
+    while !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+
+    idx
+}
+
+fn ceil_char_boundary(s: &str, mut idx: usize) -> usize {
+    while !s.is_char_boundary(idx) {
+        idx += 1;
+    }
+
+    idx
+}
+
+fn hint(err: &ErrorKind) -> Option<&'static str> {
+    Some(match err {
+        ErrorKind::Eof | ErrorKind::ExpectedValue => {
+            "the input ended before the value was complete"
+        }
+        ErrorKind::ExpectedColon => "object keys must be followed by `:`",
+        ErrorKind::UnexpectedToken => "is there a missing comma or an unquoted key?",
+        ErrorKind::UnclosedString => "add the closing `\"`",
+        ErrorKind::ControlCharacter => {
+            "control characters must be escaped, e.g. `\\n` for a newline"
+        }
+        ErrorKind::InvalidEscapeSequnce => {
+            "valid escapes are `\\\"`, `\\\\`, `\\/`, `\\b`, `\\f`, `\\n`, `\\r`, `\\t` and `\\uXXXX`"
+        }
+        ErrorKind::InvalidLiteral => "literals are `true`, `false` and `null` in lowercase",
+        ErrorKind::TrailingComma => "did you mean to allow trailing commas?",
+        ErrorKind::LeadingDecimal => "add a `0` before the decimal point",
+        ErrorKind::TrailingDecimal => "add a digit after the decimal point",
+        ErrorKind::LeadingZero => "remove the leading zeros",
+        ErrorKind::NumberOverflow => "the number is too large to be represented",
+        ErrorKind::DepthLimitExceeded => "reduce the nesting or raise the configured `max_depth`",
+        ErrorKind::StringTooLong => "shorten the string or raise the configured `max_string_len`",
+        ErrorKind::ContainerTooLarge => {
+            "reduce the number of elements or raise the configured `max_container_len`"
+        }
+        ErrorKind::AllocLimitExceeded => "raise the configured `max_alloc`",
+        ErrorKind::DuplicateKey => "remove or rename one of the keys",
+        #[cfg(feature = "std")]
+        ErrorKind::Io(_) => return None,
+    })
+}
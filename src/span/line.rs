@@ -0,0 +1,176 @@
+use crate::source::{NonVolatile, Source};
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Display},
+    ops::Range,
+    slice::from_raw_parts,
+};
+
+/// Maps byte offsets to line and column numbers.
+///
+/// It can be built from the whole source at once or incrementally by feeding the data as it
+/// is read. Lines are terminated by `\n`, which makes `\r\n` work as well.
+///
+/// # Example
+/// ```
+/// use flexon::{Error, Parser, span::{LineIndex, Span}};
+///
+/// let src = "{\n  \"ключ\": tru\n}";
+/// let err: Span<Error> = Parser::from_str(src).parse::<flexon::span::Value>().unwrap_err();
+/// let index = LineIndex::new(src);
+///
+/// assert_eq!(index.line_col(err.start()).to_string(), "2:15");
+/// assert_eq!(index.line_col_utf16(err.start()).to_string(), "2:11");
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    // starting offset of each line.
+    lines: Vec<usize>,
+    // starting offset and byte length of each non ASCII character.
+    wide: Vec<(usize, u8)>,
+    len: usize,
+}
+
+/// A 1-based line and column pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    line: usize,
+    col: usize,
+}
+
+impl LineCol {
+    /// Returns the 1-based line number.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the 1-based column number.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.col
+    }
+}
+
+impl Display for LineCol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.col)
+    }
+}
+
+impl LineIndex {
+    /// Creates a line index of the given data.
+    #[inline]
+    pub fn new<T: AsRef<[u8]>>(src: T) -> Self {
+        let mut tmp = Self::default();
+        tmp.extend(src.as_ref());
+        tmp
+    }
+
+    /// Creates a line index of the data currently held by the given source.
+    #[inline]
+    pub fn from_source<S: Source<Volatility = NonVolatile>>(src: &mut S) -> Self {
+        let len = src.len();
+        match len {
+            0 => Self::default(),
+            _ => Self::new(unsafe { from_raw_parts(src.ptr(0), len) }),
+        }
+    }
+
+    /// Appends the given data, following the data indexed so far.
+    ///
+    /// Characters split across calls are handled as well.
+    pub fn extend(&mut self, s: &[u8]) {
+        for (i, &v) in s.iter().enumerate() {
+            match v {
+                b'\n' => self.lines.push(self.len + i + 1),
+                0xC0..=0xDF => self.wide.push((self.len + i, 2)),
+                0xE0..=0xEF => self.wide.push((self.len + i, 3)),
+                0xF0.. => self.wide.push((self.len + i, 4)),
+                _ => {}
+            }
+        }
+
+        self.len += s.len();
+    }
+
+    /// Returns the number of bytes indexed so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing has been indexed yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of lines indexed so far.
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Converts the given byte offset into a line and column, where the column is counted in bytes.
+    pub fn line_col(&self, offset: usize) -> LineCol {
+        let line = self.lines.partition_point(|&v| v <= offset) - 1;
+
+        LineCol {
+            line: line + 1,
+            col: offset - self.lines[line] + 1,
+        }
+    }
+
+    /// Converts the given byte offset into a line and column, where the column is counted in characters.
+    pub(crate) fn line_col_chars(&self, offset: usize) -> LineCol {
+        let mut tmp = self.line_col(offset);
+        let start = self.lines[tmp.line - 1];
+        let a = self.wide.partition_point(|v| v.0 < start);
+        let b = self
+            .wide
+            .partition_point(|v| v.0 + (v.1 as usize) <= offset);
+
+        for &(_, len) in &self.wide[a..b.max(a)] {
+            tmp.col -= len as usize - 1;
+        }
+
+        tmp
+    }
+
+    // byte range of the given 1-based line, without the line break.
+    pub(crate) fn line_range(&self, line: usize) -> Range<usize> {
+        let start = self.lines[line - 1];
+        let end = self.lines.get(line).map_or(self.len, |v| v - 1);
+
+        start..end
+    }
+
+    /// Converts the given byte offset into a line and column, where the column is counted in UTF-16 code units.
+    pub fn line_col_utf16(&self, offset: usize) -> LineCol {
+        let mut tmp = self.line_col(offset);
+        let start = self.lines[tmp.line - 1];
+        let a = self.wide.partition_point(|v| v.0 < start);
+        let b = self
+            .wide
+            .partition_point(|v| v.0 + (v.1 as usize) <= offset);
+
+        for &(_, len) in &self.wide[a..b.max(a)] {
+            // characters outside BMP take a surrogate pair.
+            tmp.col -= len as usize - 1 - (len == 4) as usize;
+        }
+
+        tmp
+    }
+}
+
+impl Default for LineIndex {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            lines: Vec::from([0]),
+            wide: Vec::new(),
+            len: 0,
+        }
+    }
+}
@@ -1,5 +1,11 @@
 //! Span information for JSON values.
 
+#[cfg(feature = "comment")]
+mod comments;
+#[cfg(feature = "alloc")]
+mod diagnostic;
+#[cfg(feature = "alloc")]
+mod line;
 #[cfg(feature = "alloc")]
 mod value;
 
@@ -13,8 +19,15 @@ use core::{
     ops::Deref,
 };
 
+#[cfg(feature = "comment")]
+pub use comments::{Attached, CommentMap};
+
 #[cfg(feature = "alloc")]
-pub use value::GenericValue;
+pub use {
+    diagnostic::{Diagnose, Diagnostic},
+    line::{LineCol, LineIndex},
+    value::GenericValue,
+};
 
 /// Represents a borrowed JSON value.
 #[cfg(feature = "alloc")]
@@ -69,6 +82,12 @@ impl<T> Span<T> {
         Self { data, start, end }
     }
 
+    #[inline]
+    #[cfg(feature = "serde")]
+    pub(crate) fn into_data(self) -> T {
+        self.data
+    }
+
     /// Returns a reference to its value.
     #[inline]
     pub fn data(&self) -> &T {
@@ -215,9 +234,45 @@ impl<E: ErrorBuilder> ErrorBuilder for Span<E> {
         Self::new(E::unexpected_token())
     }
 
+    #[inline]
+    fn depth_limit_exceeded() -> Self {
+        Self::new(E::depth_limit_exceeded())
+    }
+
+    #[inline]
+    fn string_too_long() -> Self {
+        Self::new(E::string_too_long())
+    }
+
+    #[inline]
+    fn container_too_large() -> Self {
+        Self::new(E::container_too_large())
+    }
+
+    #[inline]
+    fn alloc_limit_exceeded() -> Self {
+        Self::new(E::alloc_limit_exceeded())
+    }
+
+    #[inline]
+    fn duplicate_key() -> Self {
+        Self::new(E::duplicate_key())
+    }
+
+    #[inline]
+    #[cfg(feature = "std")]
+    fn io(err: std::io::Error) -> Self {
+        Self::new(E::io(err))
+    }
+
     #[inline]
     fn apply_span(&mut self, start: usize, end: usize) {
         self.start = start;
         self.end = end;
     }
+
+    #[inline]
+    fn apply_offset(&mut self, offset: usize) {
+        self.data.apply_offset(offset);
+    }
 }
@@ -306,7 +306,7 @@ impl<S: Deref<Target = str>> Index<&str> for GenericValue<S> {
 impl<'a, S, V> ValueBuilder<'a, S> for Span<GenericValue<V>>
 where
     S: Source,
-    V: StringBuilder<'a, S, Span<Error>>,
+    V: StringBuilder<'a, S, Span<Error>> + Deref<Target = str>,
 {
     const LAZY: bool = false;
     const CUSTOM_LITERAL: bool = false;
@@ -331,11 +331,7 @@ where
 
     #[inline]
     fn float(val: f64) -> Self {
-        unsafe {
-            Self::new(GenericValue::Number(
-                Number::from_f64(val).unwrap_unchecked(),
-            ))
-        }
+        Self::new(GenericValue::Number(Number::from_f64_lossless(val)))
     }
 
     #[inline]
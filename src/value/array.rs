@@ -1,11 +1,15 @@
+//! JSON array representation.
+
 use crate::{misc::capacity_overflow, value::builder::ArrayBuilder};
 use alloc::alloc::{alloc, dealloc, handle_alloc_error, realloc};
 use core::{
     alloc::Layout,
     fmt::{Debug, Formatter, Result},
+    iter::FusedIterator,
+    mem::ManuallyDrop,
     ops::{Deref, DerefMut},
-    ptr::{NonNull, slice_from_raw_parts_mut},
-    slice::{from_raw_parts, from_raw_parts_mut},
+    ptr::{NonNull, copy, slice_from_raw_parts_mut},
+    slice::{Iter, IterMut, from_raw_parts, from_raw_parts_mut},
 };
 
 /// Represents a JSON array.
@@ -15,6 +19,131 @@ pub struct Array<T> {
     cap: usize,
 }
 
+impl<T> Array<T> {
+    /// Appends a value to the back of the array.
+    #[inline]
+    pub fn push(&mut self, val: T) {
+        if self.len == self.cap {
+            self.grow();
+        }
+
+        unsafe { self.buf.add(self.len).write(val) }
+        self.len += 1;
+    }
+
+    /// Removes the last value and returns it, `None` if the array is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        Some(unsafe { self.buf.add(self.len).read() })
+    }
+
+    /// Inserts a value at the given index, shifting all values after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx > len`.
+    pub fn insert(&mut self, idx: usize, val: T) {
+        assert!(idx <= self.len, "insertion index is out of bounds");
+
+        if self.len == self.cap {
+            self.grow();
+        }
+
+        unsafe {
+            let ptr = self.buf.add(idx).as_ptr();
+            copy(ptr, ptr.add(1), self.len - idx);
+            ptr.write(val);
+        }
+
+        self.len += 1;
+    }
+
+    /// Removes the value at the given index and returns it, shifting all values after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= len`.
+    pub fn remove(&mut self, idx: usize) -> T {
+        assert!(idx < self.len, "removal index is out of bounds");
+
+        self.len -= 1;
+        unsafe {
+            let ptr = self.buf.add(idx).as_ptr();
+            let val = ptr.read();
+            copy(ptr.add(1), ptr, self.len - idx);
+            val
+        }
+    }
+
+    /// Shortens the array to the given length, dropping the rest of the values.
+    ///
+    /// Has no effect if `len` is greater than or equal to the current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            let tail = slice_from_raw_parts_mut(unsafe { self.buf.add(len).as_ptr() }, self.len - len);
+            self.len = len;
+            unsafe { tail.drop_in_place() }
+        }
+    }
+
+    /// Retains only the values for which the given predicate returns `true`, preserving their order.
+    pub fn retain<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        let len = self.len;
+        let mut kept = 0;
+        // values are leaked rather than dropped twice if `f` panics.
+        self.len = 0;
+
+        for i in 0..len {
+            unsafe {
+                let ptr = self.buf.add(i).as_ptr();
+                if f(&mut *ptr) {
+                    copy(ptr, self.buf.add(kept).as_ptr(), 1);
+                    kept += 1;
+                } else {
+                    ptr.drop_in_place();
+                }
+            }
+        }
+
+        self.len = kept;
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn grow(&mut self) {
+        if let Some(new_cap) = self.len.checked_add(1).and_then(|v| v.checked_mul(2))
+            && let Ok(layout) = Layout::array::<T>(new_cap)
+        {
+            let new_buf = unsafe {
+                if self.cap != 0 {
+                    realloc(
+                        self.buf.as_ptr().cast(),
+                        Layout::array::<T>(self.cap).unwrap_unchecked(),
+                        layout.size(),
+                    )
+                } else {
+                    alloc(layout)
+                }
+            };
+
+            match NonNull::new(new_buf.cast()) {
+                Some(v) => {
+                    self.cap = new_cap;
+                    self.buf = v;
+                }
+                _ => handle_alloc_error(layout),
+            }
+        } else {
+            capacity_overflow()
+        }
+    }
+}
+
 impl<T> ArrayBuilder<T> for Array<T> {
     #[inline]
     fn new() -> Self {
@@ -52,41 +181,66 @@ impl<T> ArrayBuilder<T> for Array<T> {
 
     #[inline]
     fn on_value(&mut self, val: T) {
-        self.len += 1;
+        self.push(val)
+    }
 
-        if self.cap < self.len {
-            if let Some(new_cap) = self.len.checked_mul(2)
-                && let Ok(layout) = Layout::array::<T>(new_cap)
-            {
-                let new_buf = unsafe {
-                    if self.cap != 0 {
-                        realloc(
-                            self.buf.as_ptr().cast(),
-                            Layout::array::<T>(self.cap).unwrap_unchecked(),
-                            layout.size(),
-                        )
-                    } else {
-                        alloc(layout)
-                    }
-                };
+    #[inline]
+    fn on_complete(&mut self) {}
+}
 
-                match NonNull::new(new_buf.cast()) {
-                    Some(v) => {
-                        self.cap = new_cap;
-                        self.buf = v;
-                    }
-                    _ => handle_alloc_error(layout),
-                }
-            } else {
-                capacity_overflow()
-            }
+impl<T> Extend<T> for Array<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for v in iter {
+            self.push(v);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Array<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut arr = Self::with_capacity(iter.size_hint().0);
+        arr.extend(iter);
+        arr
+    }
+}
+
+impl<T> IntoIterator for Array<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let this = ManuallyDrop::new(self);
+        IntoIter {
+            buf: this.buf,
+            cap: this.cap,
+            start: 0,
+            end: this.len,
         }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Array<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
 
-        unsafe { self.buf.add(self.len).sub(1).write(val) }
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
+}
+
+impl<'a, T> IntoIterator for &'a mut Array<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
 
     #[inline]
-    fn on_complete(&mut self) {}
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
 }
 
 impl<T: PartialEq> PartialEq for Array<T> {
@@ -163,3 +317,77 @@ impl<T> Drop for Array<T> {
         }
     }
 }
+
+/// An iterator that moves the values out of an [`Array`].
+pub struct IntoIter<T> {
+    buf: NonNull<T>,
+    cap: usize,
+    start: usize,
+    end: usize,
+}
+
+impl<T> IntoIter<T> {
+    /// Returns the remaining values as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { from_raw_parts(self.buf.add(self.start).as_ptr(), self.end - self.start) }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.start += 1;
+        Some(unsafe { self.buf.add(self.start - 1).read() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        Some(unsafe { self.buf.add(self.end).read() })
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T: Debug> Debug for IntoIter<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        if self.cap != 0 {
+            unsafe {
+                slice_from_raw_parts_mut(self.buf.add(self.start).as_ptr(), self.end - self.start)
+                    .drop_in_place();
+                dealloc(
+                    self.buf.as_ptr().cast(),
+                    Layout::array::<T>(self.cap).unwrap_unchecked(),
+                );
+            }
+        }
+    }
+}
@@ -4,7 +4,10 @@ mod string;
 
 use crate::{
     pointer::JsonPointer,
-    value::{misc::define_value, owned},
+    value::{
+        misc::{define_value, from_pairs},
+        owned,
+    },
 };
 use core::{
     mem::replace,
@@ -28,6 +31,50 @@ impl<'a> Value<'a> {
         replace(self, Value::Null)
     }
 
+    /// Converts the value into an owned one, so that it can outlive the source.
+    ///
+    /// Strings that are already owned are moved rather than copied.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::{OwnedValue, Value};
+    ///
+    /// let src = String::from(r#"{"name": "Walter", "quote": "I am the one who \"knocks\""}"#);
+    /// let val: Value = flexon::parse(src.as_str())?;
+    /// let owned: OwnedValue = val.into_owned();
+    /// drop(src);
+    ///
+    /// assert_eq!(owned["name"].as_str(), Some("Walter"));
+    /// assert_eq!(owned.to_borrowed()["quote"].as_str(), Some(r#"I am the one who "knocks""#));
+    /// # Ok::<_, flexon::Error>(())
+    /// ```
+    pub fn into_owned(self) -> owned::Value {
+        match self {
+            Value::Null => owned::Value::Null,
+            Value::Boolean(v) => owned::Value::Boolean(v),
+            Value::Number(v) => owned::Value::Number(v),
+            Value::String(v) => owned::Value::String(v.into()),
+            Value::Array(v) => owned::Value::Array(v.into_iter().map(Value::into_owned).collect()),
+            Value::Object(v) => {
+                from_pairs(v.into_iter().map(|(k, v)| (k, v.into_owned())))
+            }
+        }
+    }
+
+    /// Copies the value into an owned one, so that it can outlive the source.
+    pub fn to_owned_value(&self) -> owned::Value {
+        match self {
+            Value::Null => owned::Value::Null,
+            Value::Boolean(v) => owned::Value::Boolean(*v),
+            Value::Number(v) => owned::Value::Number(*v),
+            Value::String(v) => owned::Value::String(v.as_str().into()),
+            Value::Array(v) => owned::Value::Array(v.iter().map(Value::to_owned_value).collect()),
+            Value::Object(v) => {
+                from_pairs(v.iter().map(|(k, v)| (k.as_str(), v.to_owned_value())))
+            }
+        }
+    }
+
     /// Returns a reference to the value associated with the given index, `None` otherwise.
     #[inline]
     pub fn get<I: JsonPointer>(&self, idx: I) -> Option<&Value<'a>> {
@@ -245,6 +292,36 @@ impl<'a> Value<'a> {
     }
 }
 
+impl owned::Value {
+    /// Returns a borrowed value with its strings borrowed from self.
+    ///
+    /// Only the arrays and objects are allocated.
+    pub fn to_borrowed(&self) -> Value<'_> {
+        match self {
+            Self::Null => Value::Null,
+            Self::Boolean(v) => Value::Boolean(*v),
+            Self::Number(v) => Value::Number(*v),
+            Self::String(v) => Value::String(v.into()),
+            Self::Array(v) => Value::Array(v.iter().map(Self::to_borrowed).collect()),
+            Self::Object(v) => from_pairs(v.iter().map(|(k, v)| (k.as_str(), v.to_borrowed()))),
+        }
+    }
+}
+
+impl From<Value<'_>> for owned::Value {
+    #[inline]
+    fn from(val: Value<'_>) -> Self {
+        val.into_owned()
+    }
+}
+
+impl<'a> From<&'a owned::Value> for Value<'a> {
+    #[inline]
+    fn from(val: &'a owned::Value) -> Self {
+        val.to_borrowed()
+    }
+}
+
 impl PartialEq<Value<'_>> for owned::Value {
     fn eq(&self, other: &Value<'_>) -> bool {
         match (self, other) {
@@ -6,8 +6,8 @@ use crate::{
 };
 use alloc::alloc::{alloc, dealloc, handle_alloc_error, realloc};
 use core::{
-    alloc::Layout, hint::unreachable_unchecked, ptr::dangling_mut, slice::from_raw_parts,
-    str::from_utf8_unchecked,
+    alloc::Layout, hint::unreachable_unchecked, mem::ManuallyDrop, ptr::dangling_mut,
+    slice::from_raw_parts, str::from_utf8_unchecked,
 };
 
 /// Represents a borrowed JSON string.
@@ -33,6 +33,16 @@ impl<'a> String<'a> {
         Self(Inner::Heap { buf, len, cap })
     }
 
+    /// Returns the string slice if it is borrowed from the source.
+    #[inline]
+    #[cfg(feature = "serde")]
+    pub(crate) fn as_borrowed(&self) -> Option<&'a str> {
+        match self.0 {
+            Inner::Ref(v) => Some(unsafe { from_utf8_unchecked(v) }),
+            _ => None,
+        }
+    }
+
     /// Returns itself as a string slice.
     #[inline]
     pub fn as_str(&'a self) -> &'a str {
@@ -113,7 +123,7 @@ where
             unreachable_unchecked()
         };
 
-        if likely(*len == 0) {
+        if likely(*cap == 0) {
             return self.0 = Inner::Ref(s);
         }
 
@@ -168,6 +178,28 @@ impl PartialEq<owned::String> for String<'_> {
     }
 }
 
+impl From<String<'_>> for owned::String {
+    /// Converts the string into an owned one.
+    ///
+    /// This does not allocate or copy memory if the string is already owned.
+    #[inline]
+    fn from(value: String<'_>) -> Self {
+        let value = ManuallyDrop::new(value);
+        match value.0 {
+            Inner::Ref(v) => unsafe { from_utf8_unchecked(v) }.into(),
+            Inner::Heap { buf, len, cap } => owned::String::from_raw_parts(buf, len, cap),
+        }
+    }
+}
+
+impl<'a> From<&'a owned::String> for String<'a> {
+    /// Creates a borrowed string from the owned one.
+    #[inline]
+    fn from(value: &'a owned::String) -> Self {
+        String(Inner::Ref(value.as_str().as_bytes()))
+    }
+}
+
 impl<'a> From<&'a str> for String<'a> {
     /// Creates a borrowed string from the string slice.
     #[inline]
@@ -194,6 +226,7 @@ impl From<String<'_>> for alloc::string::String {
     fn from(value: String<'_>) -> Self {
         use alloc::string::String;
 
+        let value = ManuallyDrop::new(value);
         unsafe {
             match value.0 {
                 Inner::Ref(v) => String::from_utf8_unchecked(v.into()),
@@ -38,6 +38,8 @@ pub trait ValueBuilder<'a, S: Source>: Sized {
     /// contains only contiguous ASCII bytes, excluding the following:
     ///
     /// `'{'`, `'}'`, `'['`, `']'`, `'"'`, `':'`, `','`, `'/'`, `' '`, `'\n'`, `'\t'`, `'\r'`, `'\0'`
+    ///
+    /// Along with `'#'` when hash comments are enabled.
     fn literal(s: &'a [u8]) -> Result<Self, Self::Error>;
 
     /// Creates a value by the given integer value.
@@ -97,6 +99,24 @@ pub trait ObjectBuilder<K, V> {
     /// Adds a key-value to the object.
     fn on_value(&mut self, key: K, val: V);
 
+    /// Returns the index of the entry with the given key, `None` otherwise.
+    ///
+    /// Only called when the configured [`DuplicateKeys`](crate::config::DuplicateKeys) policy is
+    /// other than `Allow`. Defaults to `None`, which treats every key as unique.
+    #[inline]
+    fn find(&self, key: &K) -> Option<usize> {
+        _ = key;
+        None
+    }
+
+    /// Replaces the value of the entry at the given index, as returned by [`ObjectBuilder::find`].
+    ///
+    /// Only called when the configured [`DuplicateKeys`](crate::config::DuplicateKeys) policy is `LastWins`.
+    #[inline]
+    fn replace(&mut self, idx: usize, val: V) {
+        _ = (idx, val);
+    }
+
     /// Called when object parsing completes, e.g., for sorting.
     fn on_complete(&mut self);
 }
@@ -184,6 +204,82 @@ pub trait ErrorBuilder {
     /// Unexpected token while parsing.
     fn unexpected_token() -> Self;
 
+    /// Nesting depth of arrays and objects exceeded the configured limit.
+    ///
+    /// Defaults to [`ErrorBuilder::unexpected_token`].
+    #[inline]
+    fn depth_limit_exceeded() -> Self
+    where
+        Self: Sized,
+    {
+        Self::unexpected_token()
+    }
+
+    /// String length exceeded the configured limit.
+    ///
+    /// Defaults to [`ErrorBuilder::unexpected_token`].
+    #[inline]
+    fn string_too_long() -> Self
+    where
+        Self: Sized,
+    {
+        Self::unexpected_token()
+    }
+
+    /// Number of elements in an array or entries in an object exceeded the configured limit.
+    ///
+    /// Defaults to [`ErrorBuilder::unexpected_token`].
+    #[inline]
+    fn container_too_large() -> Self
+    where
+        Self: Sized,
+    {
+        Self::unexpected_token()
+    }
+
+    /// Allocated bytes exceeded the configured limit.
+    ///
+    /// Defaults to [`ErrorBuilder::unexpected_token`].
+    #[inline]
+    fn alloc_limit_exceeded() -> Self
+    where
+        Self: Sized,
+    {
+        Self::unexpected_token()
+    }
+
+    /// Object contains the same key more than once.
+    ///
+    /// Defaults to [`ErrorBuilder::unexpected_token`].
+    #[inline]
+    fn duplicate_key() -> Self
+    where
+        Self: Sized,
+    {
+        Self::unexpected_token()
+    }
+
+    /// I/O error while reading the source.
+    ///
+    /// Defaults to [`ErrorBuilder::eof`], as the source stops yielding data on failure.
+    #[inline]
+    #[cfg(feature = "std")]
+    fn io(err: std::io::Error) -> Self
+    where
+        Self: Sized,
+    {
+        _ = err;
+        Self::eof()
+    }
+
     /// Applies span information. The given offsets will be byte offsets.
     fn apply_span(&mut self, start: usize, end: usize);
+
+    /// Records the byte offset of where parsing stopped, called once before the error is returned.
+    ///
+    /// Does nothing by default.
+    #[inline]
+    fn apply_offset(&mut self, offset: usize) {
+        _ = offset;
+    }
 }
@@ -0,0 +1,195 @@
+//! Hash index over the keys of large objects.
+
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+/// Objects with at least this many members get indexed.
+pub(crate) const THRESHOLD: usize = 16;
+
+/// Longest probe sequence allowed while inserting before giving up on the index.
+const MAX_PROBE: usize = 64;
+
+/// Open addressing table with linear probing, mapping keys to their first occurrence.
+///
+/// Each slot holds the upper half of the key's hash along with the entry index plus one,
+/// zero being an empty slot. The table is kept at most half full.
+///
+/// Keys colliding enough to exceed [`MAX_PROBE`], as crafted ones would, drop the index so
+/// that lookups fall back to scanning the entries rather than degrading further.
+#[derive(Clone, Default)]
+pub(crate) struct HashIndex {
+    slots: Vec<u64>,
+    // the index was dropped due to collisions, and is not rebuilt until cleared.
+    flooded: bool,
+}
+
+impl HashIndex {
+    /// Whether the index is built, otherwise lookups have to scan the entries.
+    #[inline]
+    pub fn is_built(&self) -> bool {
+        !self.slots.is_empty()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.flooded = false;
+    }
+
+    /// Rebuilds the index from scratch, or clears it if there are too few or too many entries.
+    pub fn build<K: Deref<Target = str>, V>(&mut self, entries: &[(K, V)]) {
+        self.slots.clear();
+
+        if self.flooded || entries.len() < THRESHOLD || entries.len() > u32::MAX as usize {
+            return;
+        }
+
+        self.slots.resize((entries.len() * 2).next_power_of_two(), 0);
+        for (i, (k, _)) in entries.iter().enumerate() {
+            if !self.insert(entries, k, i) {
+                return self.flood();
+            }
+        }
+    }
+
+    /// Indexes the last entry, which must have been just appended.
+    ///
+    /// The index is built if it is not already.
+    #[inline]
+    pub fn push<K: Deref<Target = str>, V>(&mut self, entries: &[(K, V)]) {
+        let len = entries.len();
+
+        if len * 2 > self.slots.len() || len > u32::MAX as usize {
+            return self.build(entries);
+        }
+
+        if !self.insert(entries, &entries[len - 1].0, len - 1) {
+            self.flood();
+        }
+    }
+
+    /// Returns the index of the first entry with the given key, the index must be built.
+    #[inline]
+    pub fn find<K: Deref<Target = str>, V>(&self, entries: &[(K, V)], key: &str) -> Option<usize> {
+        let hash = hash(key.as_bytes());
+        let mask = self.slots.len() - 1;
+        let mut i = hash as usize & mask;
+
+        loop {
+            let slot = self.slots[i];
+            if slot == 0 {
+                return None;
+            }
+
+            let idx = (slot as u32 - 1) as usize;
+            if slot >> 32 == hash >> 32 && &*entries[idx].0 == key {
+                return Some(idx);
+            }
+
+            i = (i + 1) & mask;
+        }
+    }
+
+    // keeps the existing slot for duplicate keys, so that lookups match a linear scan.
+    // returns `false` if the probe sequence got too long.
+    fn insert<K: Deref<Target = str>, V>(
+        &mut self,
+        entries: &[(K, V)],
+        key: &str,
+        idx: usize,
+    ) -> bool {
+        let hash = hash(key.as_bytes());
+        let mask = self.slots.len() - 1;
+        let mut i = hash as usize & mask;
+
+        for _ in 0..MAX_PROBE {
+            let slot = self.slots[i];
+            if slot == 0 {
+                self.slots[i] = hash & !0xFFFF_FFFF | (idx as u64 + 1);
+                return true;
+            }
+
+            if slot >> 32 == hash >> 32 && &*entries[(slot as u32 - 1) as usize].0 == key {
+                return true;
+            }
+
+            i = (i + 1) & mask;
+        }
+
+        false
+    }
+
+    #[cold]
+    fn flood(&mut self) {
+        self.slots = Vec::new();
+        self.flooded = true;
+    }
+}
+
+// odd multiplier of the hash, random for every process when possible.
+#[inline]
+fn seed() -> u64 {
+    #[cfg(feature = "std")]
+    {
+        use core::hash::BuildHasher;
+        use std::{hash::RandomState, sync::OnceLock};
+
+        static SEED: OnceLock<u64> = OnceLock::new();
+        *SEED.get_or_init(|| RandomState::new().hash_one(0xF135_7AEA_2E62_A9C5_u64) | 1)
+    }
+
+    #[cfg(not(feature = "std"))]
+    0xF135_7AEA_2E62_A9C5
+}
+
+// multiplicative hash over 8 byte words. not resistant to hash flooding by itself,
+// which is what the seed and the probe limit are for.
+#[inline]
+fn hash(bytes: &[u8]) -> u64 {
+    let seed = seed();
+    let mut hash = bytes.len() as u64;
+    let mut chunks = bytes.chunks_exact(8);
+
+    for chunk in &mut chunks {
+        let word = u64::from_le_bytes(chunk.try_into().unwrap());
+        hash = (hash ^ word).wrapping_mul(seed);
+    }
+
+    let rem = chunks.remainder();
+    if !rem.is_empty() {
+        let mut buf = [0; 8];
+        buf[..rem.len()].copy_from_slice(rem);
+        hash = (hash ^ u64::from_le_bytes(buf)).wrapping_mul(seed);
+    }
+
+    hash = (hash ^ hash >> 32).wrapping_mul(seed);
+    hash ^ hash >> 32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, string::String};
+
+    #[test]
+    fn flooded_index_stays_dropped() {
+        let mut entries: Vec<(String, ())> = (0..THRESHOLD * 4).map(|i| (format!("{i}"), ())).collect();
+        let mut index = HashIndex::default();
+
+        index.build(&entries);
+        assert!(index.is_built());
+        for (i, (k, _)) in entries.iter().enumerate() {
+            assert_eq!(index.find(&entries, k), Some(i));
+        }
+
+        index.flood();
+        entries.push((String::from("x"), ()));
+        index.push(&entries);
+        index.build(&entries);
+        assert!(!index.is_built());
+
+        index.clear();
+        index.push(&entries);
+        assert_eq!(index.find(&entries, "x"), Some(entries.len() - 1));
+    }
+}
@@ -0,0 +1,152 @@
+//! Support for the [`json!`](crate::json) macro.
+
+use crate::value::{Array, Object, builder::ObjectBuilder};
+use core::ops::Deref;
+
+/// Value types that can be built by the [`json!`](crate::json) macro.
+pub trait JsonValue: Sized + From<Array<Self>> + From<Object<Self::String, Self>> {
+    /// The string type of the value.
+    type String: Deref<Target = str>;
+
+    /// Returns a null value.
+    fn null() -> Self;
+}
+
+#[inline(always)]
+pub fn null<V: JsonValue>() -> V {
+    V::null()
+}
+
+#[inline(always)]
+pub fn from<V: JsonValue + From<T>, T>(val: T) -> V {
+    V::from(val)
+}
+
+#[inline]
+pub fn array<V: JsonValue, const N: usize>(val: [V; N]) -> V {
+    Array::from_iter(val).into()
+}
+
+#[inline(always)]
+pub fn object<V: JsonValue>() -> Object<V::String, V> {
+    Object::new()
+}
+
+#[inline(always)]
+pub fn insert<V: JsonValue, K: Into<V::String>>(obj: &mut Object<V::String, V>, key: K, val: V) {
+    obj.insert(key.into(), val);
+}
+
+#[inline(always)]
+pub fn finish<V: JsonValue>(obj: Object<V::String, V>) -> V {
+    obj.into()
+}
+
+/// Creates a JSON value from JSON-like syntax.
+///
+/// Builds either [`OwnedValue`](crate::OwnedValue) or [`Value`](crate::Value), depending on the
+/// expected type. Any Rust expression that converts into the value can be interpolated, and object
+/// keys can be any expression that converts into the string type. Later duplicate keys replace
+/// the earlier ones.
+///
+/// # Example
+/// ```
+/// use flexon::{OwnedValue, json};
+///
+/// let name = "Walter";
+/// let tags = vec!["chemist", "teacher"];
+/// let val: OwnedValue = json!({
+///     "name": name,
+///     "age": 50,
+///     "tags": tags,
+///     "address": null,
+///     "cars": [{ "model": "Aztek", "year": 2001 }],
+///     format!("{name}_id"): Some(1.5),
+/// });
+///
+/// assert_eq!(val["name"].as_str(), Some("Walter"));
+/// assert_eq!(val["tags"][1].as_str(), Some("teacher"));
+/// assert!(val["address"].is_null());
+/// assert_eq!(val["cars"][0]["year"].as_u64(), Some(2001));
+/// assert_eq!(val["Walter_id"].as_f64(), Some(1.5));
+/// ```
+#[macro_export]
+macro_rules! json {
+    (null) => {
+        $crate::value::json::null()
+    };
+
+    ([]) => {
+        $crate::value::json::array([])
+    };
+
+    ([ $($tt:tt)+ ]) => {
+        $crate::json_internal!(@array [] $($tt)+)
+    };
+
+    ({}) => {
+        $crate::value::json::finish($crate::value::json::object())
+    };
+
+    ({ $($tt:tt)+ }) => {{
+        let mut obj = $crate::value::json::object();
+        $crate::json_internal!(@object obj () ($($tt)+));
+        $crate::value::json::finish(obj)
+    }};
+
+    ($other:expr) => {
+        $crate::value::json::from($other)
+    };
+}
+
+// munches the elements of arrays and the members of objects.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! json_internal {
+    (@array [$($elems:expr,)*]) => {
+        $crate::value::json::array([$($elems,)*])
+    };
+
+    (@array [$($elems:expr,)*] null $(, $($rest:tt)*)?) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json!(null),] $($($rest)*)?)
+    };
+
+    (@array [$($elems:expr,)*] [$($arr:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json!([$($arr)*]),] $($($rest)*)?)
+    };
+
+    (@array [$($elems:expr,)*] {$($obj:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json!({$($obj)*}),] $($($rest)*)?)
+    };
+
+    (@array [$($elems:expr,)*] $next:expr $(, $($rest:tt)*)?) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json!($next),] $($($rest)*)?)
+    };
+
+    (@object $obj:ident () ()) => {};
+
+    (@object $obj:ident ($($key:tt)+) (: null $(, $($rest:tt)*)?)) => {
+        $crate::value::json::insert(&mut $obj, ($($key)+), $crate::json!(null));
+        $crate::json_internal!(@object $obj () ($($($rest)*)?));
+    };
+
+    (@object $obj:ident ($($key:tt)+) (: [$($arr:tt)*] $(, $($rest:tt)*)?)) => {
+        $crate::value::json::insert(&mut $obj, ($($key)+), $crate::json!([$($arr)*]));
+        $crate::json_internal!(@object $obj () ($($($rest)*)?));
+    };
+
+    (@object $obj:ident ($($key:tt)+) (: {$($val:tt)*} $(, $($rest:tt)*)?)) => {
+        $crate::value::json::insert(&mut $obj, ($($key)+), $crate::json!({$($val)*}));
+        $crate::json_internal!(@object $obj () ($($($rest)*)?));
+    };
+
+    (@object $obj:ident ($($key:tt)+) (: $val:expr $(, $($rest:tt)*)?)) => {
+        $crate::value::json::insert(&mut $obj, ($($key)+), $crate::json!($val));
+        $crate::json_internal!(@object $obj () ($($($rest)*)?));
+    };
+
+    // the key is every token up to the colon.
+    (@object $obj:ident ($($key:tt)*) ($tt:tt $($rest:tt)*)) => {
+        $crate::json_internal!(@object $obj ($($key)* $tt) ($($rest)*));
+    };
+}
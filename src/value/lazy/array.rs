@@ -1,5 +1,6 @@
 use crate::{
     Parser,
+    index::Indexed,
     value::lazy::{Raw, Value},
 };
 use alloc::vec::Vec;
@@ -11,14 +12,16 @@ use core::ops::{Index, IndexMut};
 #[derive(Debug)]
 pub struct Array<'a> {
     pub(super) raw: &'a str,
+    pub(super) at: Option<Indexed<'a>>,
     buf: Vec<(usize, Value<'a>)>,
 }
 
 impl<'a> Array<'a> {
     #[inline]
-    pub(super) fn new(s: &'a str) -> Self {
+    pub(super) fn new(s: &'a str, at: Option<Indexed<'a>>) -> Self {
         Self {
             raw: s,
+            at,
             buf: Vec::new(),
         }
     }
@@ -26,33 +29,39 @@ impl<'a> Array<'a> {
     /// Returns the raw JSON object.
     #[inline]
     pub fn raw(&self) -> Raw<'a> {
-        Raw(self.raw)
+        Raw(self.raw, self.at)
     }
 
     /// Returns a mutable reference to the value at the given index, skipping and finding if necessary.
-    pub fn get(&mut self, mut idx: usize) -> Option<&mut Value<'a>> {
+    pub fn get(&mut self, idx: usize) -> Option<&mut Value<'a>> {
         for (i, v) in unsafe { &mut *(&mut self.buf as *mut Vec<_>) } {
             if *i == idx {
                 return Some(v);
             }
         }
 
+        if let Some(at) = self.at {
+            self.buf.push((idx, Value::Raw(at.step(idx)?.raw())));
+            return self.buf.last_mut().map(|v| &mut v.1);
+        }
+
         let mut tmp = unsafe { Parser::new(self.raw.get_unchecked(1..)) };
+        let mut rem = idx;
 
         loop {
             match tmp.skip_whitespace() {
                 b',' => continue,
                 b']' => return None,
-                _ if idx == 0 => unsafe {
+                _ if rem == 0 => unsafe {
                     self.buf.push((
                         idx,
-                        Value::Raw(Raw(self.raw.get_unchecked(tmp.idx() + 1..))),
+                        Value::Raw(Raw(self.raw.get_unchecked(tmp.idx() + 1..), None)),
                     ));
 
                     return Some(&mut self.buf.last_mut().unwrap_unchecked().1);
                 },
                 v => unsafe {
-                    idx -= 1;
+                    rem -= 1;
                     match v {
                         b'"' => tmp.skip_string_unchecked(),
                         b'{' | b'[' => tmp.skip_container_unchecked(),
@@ -64,7 +73,14 @@ impl<'a> Array<'a> {
     }
 
     /// Returns the actual number of elements by skipping and counting.
+    ///
+    /// Arrays obtained from a [`StructuralIndex`](crate::index::StructuralIndex) read the
+    /// count from the index instead.
     pub fn actual_len(&self) -> usize {
+        if let Some(at) = self.at {
+            return at.count();
+        }
+
         let mut count = 0;
         let mut tmp = unsafe { Parser::new(self.raw.get_unchecked(1..)) };
 
@@ -89,6 +105,63 @@ impl<'a> Array<'a> {
     pub fn parsed_len(&self) -> usize {
         self.buf.len()
     }
+
+    /// Returns an iterator over the elements, taking the parsed ones from the cache.
+    #[inline]
+    #[cfg(feature = "serde")]
+    pub(crate) fn into_values(self) -> IntoValues<'a> {
+        IntoValues {
+            tmp: unsafe { Parser::new(self.raw.get_unchecked(1..)) },
+            done: false,
+            arr: self,
+            idx: 0,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) struct IntoValues<'a> {
+    arr: Array<'a>,
+    tmp: Parser<'a, &'a str>,
+    done: bool,
+    idx: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> Iterator for IntoValues<'a> {
+    type Item = Value<'a>;
+
+    fn next(&mut self) -> Option<Value<'a>> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            match self.tmp.skip_whitespace() {
+                b',' => continue,
+                b']' => {
+                    self.done = true;
+                    return None;
+                }
+                v => unsafe {
+                    let idx = self.idx;
+                    let val = match self.arr.buf.iter().position(|(i, _)| *i == idx) {
+                        Some(i) => self.arr.buf.swap_remove(i).1,
+                        _ => Value::Raw(Raw(self.arr.raw.get_unchecked(self.tmp.idx() + 1..), None)),
+                    };
+
+                    self.idx += 1;
+                    match v {
+                        b'"' => self.tmp.skip_string_unchecked(),
+                        b'{' | b'[' => self.tmp.skip_container_unchecked(),
+                        _ => self.tmp.skip_literal_unchecked(),
+                    }
+
+                    return Some(val);
+                },
+            }
+        }
+    }
 }
 
 impl<'a> Index<usize> for Array<'a> {
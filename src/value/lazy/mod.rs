@@ -6,6 +6,7 @@ mod object;
 
 use crate::{
     Error, Parser,
+    index::Indexed,
     pointer::JsonPointer,
     source::{NonVolatile, Source},
     value::{Number, borrowed::String, builder::ValueBuilder, lazy::dummy::*},
@@ -18,9 +19,13 @@ use core::{
 
 pub use {array::Array, object::Object};
 
+#[cfg(feature = "serde")]
+pub(crate) use {array::IntoValues, object::IntoMembers};
+
 /// Represents a lazy JSON value.
 ///
 /// The initial value will always be [`Value::Raw`] until you call its mutating APIs.
+/// Only plain JSON can be navigated, so it must not make use of a dialect or comments.
 pub enum Value<'a> {
     /// Represents a JSON null value.
     Null,
@@ -47,8 +52,7 @@ pub enum Value<'a> {
 /// Represents an unparsed JSON value.
 ///
 /// It may have trailing characters that are irrelevant to its type.
-#[repr(transparent)]
-pub struct Raw<'a>(&'a str);
+pub struct Raw<'a>(pub(crate) &'a str, pub(crate) Option<Indexed<'a>>);
 
 impl<'a> Raw<'a> {
     /// Trims the raw JSON value to its end excluding the trailing characters that are irrelevant to its type.
@@ -67,9 +71,9 @@ impl<'a> Value<'a> {
         match self {
             Value::Array(v) => v.get(idx.as_index()?),
             Value::Object(v) => v.get(idx.as_key()?),
-            Value::Raw(Raw(v)) => match unsafe { *v.as_ptr() } {
+            Value::Raw(Raw(v, at)) => match unsafe { *v.as_ptr() } {
                 b'[' => unsafe {
-                    *self = Self::Array(Array::new(v));
+                    *self = Self::Array(Array::new(v, *at));
 
                     match self {
                         Self::Array(v) => v.get(idx.as_index()?),
@@ -77,7 +81,7 @@ impl<'a> Value<'a> {
                     }
                 },
                 b'{' => unsafe {
-                    *self = Self::Object(Object::new(v));
+                    *self = Self::Object(Object::new(v, *at));
 
                     match self {
                         Self::Object(v) => v.get(idx.as_key()?),
@@ -94,7 +98,7 @@ impl<'a> Value<'a> {
     #[inline]
     pub fn as_raw(&'a self) -> Option<Raw<'a>> {
         match self {
-            Self::Raw(v) => Some(Raw(v)),
+            Self::Raw(v) => Some(Raw(v.0, v.1)),
             _ => None,
         }
     }
@@ -131,8 +135,8 @@ impl<'a> Value<'a> {
     pub fn as_array(&mut self) -> Option<&mut Array<'a>> {
         Some(match *self {
             Self::Array(ref mut v) => v,
-            Self::Raw(Raw(s)) if unsafe { *s.as_ptr() == b'[' } => unsafe {
-                *self = Self::Array(Array::new(s));
+            Self::Raw(Raw(s, at)) if unsafe { *s.as_ptr() == b'[' } => unsafe {
+                *self = Self::Array(Array::new(s, at));
 
                 match self {
                     Self::Array(v) => v,
@@ -148,7 +152,7 @@ impl<'a> Value<'a> {
     pub fn into_array(self) -> Option<Array<'a>> {
         Some(match self {
             Self::Array(v) => v,
-            Self::Raw(Raw(s)) if unsafe { *s.as_ptr() == b'[' } => Array::new(s),
+            Self::Raw(Raw(s, at)) if unsafe { *s.as_ptr() == b'[' } => Array::new(s, at),
             _ => return None,
         })
     }
@@ -158,8 +162,8 @@ impl<'a> Value<'a> {
     pub fn as_object(&mut self) -> Option<&mut Object<'a>> {
         Some(match *self {
             Self::Object(ref mut v) => v,
-            Self::Raw(Raw(s)) if unsafe { *s.as_ptr() == b'{' } => unsafe {
-                *self = Self::Object(Object::new(s));
+            Self::Raw(Raw(s, at)) if unsafe { *s.as_ptr() == b'{' } => unsafe {
+                *self = Self::Object(Object::new(s, at));
 
                 match self {
                     Self::Object(v) => v,
@@ -175,7 +179,7 @@ impl<'a> Value<'a> {
     pub fn into_object(self) -> Option<Object<'a>> {
         Some(match self {
             Self::Object(v) => v,
-            Self::Raw(Raw(s)) if unsafe { *s.as_ptr() == b'{' } => Object::new(s),
+            Self::Raw(Raw(s, at)) if unsafe { *s.as_ptr() == b'{' } => Object::new(s, at),
             _ => return None,
         })
     }
@@ -185,7 +189,7 @@ impl<'a> Value<'a> {
     pub fn as_number(&mut self) -> Option<Number> {
         Some(match *self {
             Self::Number(v) => v,
-            Self::Raw(Raw(s))
+            Self::Raw(Raw(s, _))
                 if unsafe {
                     matches!(
                         *s.as_ptr(),
@@ -230,7 +234,7 @@ impl<'a> Value<'a> {
     pub fn as_str(&mut self) -> Option<&str> {
         Some(match *self {
             Self::String(ref mut v) => v,
-            Self::Raw(Raw(s)) if unsafe { *s.as_ptr() == b'"' } => unsafe {
+            Self::Raw(Raw(s, _)) if unsafe { *s.as_ptr() == b'"' } => unsafe {
                 let mut tmp = Parser::new(s);
 
                 tmp.inc(1);
@@ -325,6 +329,8 @@ impl<'a> Value<'a> {
     /// as it doesn't cache the values. If the path is empty, then the root value is returned.
     /// The returned value will generally be a raw value unless it is a string in which case
     /// it will return a borrowed string. Numbers, booleans and nulls are returned as is.
+    /// Values obtained from a [`StructuralIndex`](crate::index::StructuralIndex) jump through
+    /// the index instead of scanning.
     ///
     /// # Example
     /// ```
@@ -342,10 +348,10 @@ impl<'a> Value<'a> {
         P: IntoIterator,
         P::Item: JsonPointer,
     {
-        let src: &'a str = match self {
-            Value::Raw(v) => v,
-            Value::Object(v) => v.raw,
-            Value::Array(array) => array.raw,
+        let (src, at): (&'a str, _) = match self {
+            Value::Raw(v) => (v.0, v.1),
+            Value::Object(v) => (v.raw, v.at),
+            Value::Array(v) => (v.raw, v.at),
             _ => unsafe {
                 return Some(match *self {
                     // valid borrowed string even if the original string is now in heap
@@ -358,9 +364,17 @@ impl<'a> Value<'a> {
                 });
             },
         };
+        if let Some(mut at) = at {
+            for pointer in p {
+                at = at.step(pointer)?;
+            }
+
+            return Some(Value::Raw(at.raw()));
+        }
+
         let mut iter = p.into_iter();
         let Some(mut pointer) = iter.next() else {
-            return Some(Value::Raw(Raw(src)));
+            return Some(Value::Raw(Raw(src, None)));
         };
         let mut tmp = Parser::new(src);
         let mut char = tmp.skip_whitespace();
@@ -423,7 +437,7 @@ impl<'a> Value<'a> {
             }
         }
 
-        unsafe { Some(Value::Raw(Raw(src.get_unchecked(tmp.idx()..)))) }
+        unsafe { Some(Value::Raw(Raw(src.get_unchecked(tmp.idx()..), None))) }
     }
 }
 
@@ -489,7 +503,7 @@ impl<'a, S: Source<Volatility = NonVolatile>> ValueBuilder<'a, S> for Value<'a>
 
     #[inline]
     fn raw(s: &'a [u8]) -> Self {
-        Self::Raw(Raw(unsafe { str::from_utf8_unchecked(s) }))
+        Self::Raw(Raw(unsafe { str::from_utf8_unchecked(s) }, None))
     }
 
     #[inline]
@@ -1,5 +1,7 @@
 use crate::{
-    Error, Parser, value::{
+    Error, Parser,
+    index::Indexed,
+    value::{
         borrowed::String,
         lazy::{Raw, Value},
     },
@@ -16,14 +18,16 @@ use core::{
 #[derive(Debug)]
 pub struct Object<'a> {
     pub(super) raw: &'a str,
+    pub(super) at: Option<Indexed<'a>>,
     buf: Vec<(String<'a>, Value<'a>)>,
 }
 
 impl<'a> Object<'a> {
     #[inline]
-    pub(super) fn new(s: &'a str) -> Self {
+    pub(super) fn new(s: &'a str, at: Option<Indexed<'a>>) -> Self {
         Self {
             raw: s,
+            at,
             buf: Vec::new(),
         }
     }
@@ -31,7 +35,7 @@ impl<'a> Object<'a> {
     /// Returns the raw JSON object.
     #[inline]
     pub fn raw(&self) -> Raw<'a> {
-        Raw(self.raw)
+        Raw(self.raw, self.at)
     }
 
     /// Returns a mutable reference to the value associated with the given key, skipping and finding if necessary.
@@ -42,6 +46,16 @@ impl<'a> Object<'a> {
             }
         }
 
+        if let Some(at) = self.at {
+            let val = at.step(key)?;
+            let mut tmp = Parser::new(self.raw);
+            tmp.inc(val.key() - at.off + 1);
+
+            let new = unsafe { tmp.string_unchecked::<String, String, Error>() };
+            self.buf.push((new, Value::Raw(val.raw())));
+            return self.buf.last_mut().map(|v| &mut v.1);
+        }
+
         let mut tmp = unsafe { Parser::new(self.raw.get_unchecked(1..)) };
 
         loop {
@@ -54,7 +68,7 @@ impl<'a> Object<'a> {
                     if &*new == key {
                         self.buf.push((
                             new,
-                            Value::Raw(Raw(self.raw.get_unchecked(tmp.idx() + 1..))),
+                            Value::Raw(Raw(self.raw.get_unchecked(tmp.idx() + 1..), None)),
                         ));
 
                         return Some(&mut self.buf.last_mut().unwrap_unchecked().1);
@@ -74,7 +88,14 @@ impl<'a> Object<'a> {
     }
 
     /// Returns the actual number of elements by skipping and counting.
+    ///
+    /// Objects obtained from a [`StructuralIndex`](crate::index::StructuralIndex) read the
+    /// count from the index instead.
     pub fn actual_len(&self) -> usize {
+        if let Some(at) = self.at {
+            return at.count();
+        }
+
         let mut count = 0;
         let mut tmp = unsafe { Parser::new(self.raw.get_unchecked(1..)) };
 
@@ -99,6 +120,65 @@ impl<'a> Object<'a> {
     pub fn parsed_len(&self) -> usize {
         self.buf.len()
     }
+
+    /// Returns an iterator over the members, taking the parsed values from the cache.
+    #[inline]
+    #[cfg(feature = "serde")]
+    pub(crate) fn into_members(self) -> IntoMembers<'a> {
+        IntoMembers {
+            tmp: unsafe { Parser::new(self.raw.get_unchecked(1..)) },
+            done: false,
+            obj: self,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) struct IntoMembers<'a> {
+    obj: Object<'a>,
+    tmp: Parser<'a, &'a str>,
+    done: bool,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> Iterator for IntoMembers<'a> {
+    type Item = (String<'a>, Value<'a>);
+
+    fn next(&mut self) -> Option<(String<'a>, Value<'a>)> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            match self.tmp.skip_whitespace() {
+                b'"' => unsafe {
+                    let key = self.tmp.string_unchecked::<String, String, Error>();
+                    self.tmp.skip_whitespace(); // skip ':'
+                    let wtf = self.tmp.skip_whitespace();
+
+                    // only the first occurrence of a key is ever cached
+                    let val = match self.obj.buf.iter().position(|(k, _)| **k == *key) {
+                        Some(i) => self.obj.buf.swap_remove(i).1,
+                        _ => Value::Raw(Raw(self.obj.raw.get_unchecked(self.tmp.idx() + 1..), None)),
+                    };
+
+                    match wtf {
+                        b'"' => self.tmp.skip_string_unchecked(),
+                        b'{' | b'[' => self.tmp.skip_container_unchecked(),
+                        _ => self.tmp.skip_literal_unchecked(),
+                    }
+
+                    return Some((key, val));
+                },
+                b',' => continue,
+                b'}' => {
+                    self.done = true;
+                    return None;
+                }
+                _ => unsafe { unreachable_unchecked() },
+            }
+        }
+    }
 }
 
 impl<'a> Index<&str> for Object<'a> {
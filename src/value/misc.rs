@@ -1,3 +1,5 @@
+use crate::value::{Object, builder::ObjectBuilder, json::JsonValue};
+
 macro_rules! define_value {
     (
         $(#[$meta:meta])+
@@ -59,7 +61,7 @@ macro_rules! define_value {
 
             #[inline]
             fn float(val: f64) -> Self {
-                unsafe { Self::Number(Number::from_f64(val).unwrap_unchecked()) }
+                Self::Number(Number::from_f64_lossless(val))
             }
 
             #[inline]
@@ -123,6 +125,67 @@ macro_rules! define_value {
             }
         }
 
+        impl $(<$name_lt>)? From<alloc::string::String> for $name $(<$name_lt>)? {
+            #[inline]
+            fn from(val: alloc::string::String) -> $name $(<$name_lt>)? {
+                $name::String(val.into())
+            }
+        }
+
+        impl<$($name_lt,)? T: Into<$name $(<$name_lt>)?>> From<Option<T>> for $name $(<$name_lt>)? {
+            #[inline]
+            fn from(val: Option<T>) -> $name $(<$name_lt>)? {
+                match val {
+                    Some(v) => v.into(),
+                    None => $name::Null,
+                }
+            }
+        }
+
+        impl<$($name_lt,)? T: Into<$name $(<$name_lt>)?>> From<alloc::vec::Vec<T>> for $name $(<$name_lt>)? {
+            #[inline]
+            fn from(val: alloc::vec::Vec<T>) -> $name $(<$name_lt>)? {
+                $name::Array(val.into_iter().map(Into::into).collect())
+            }
+        }
+
+        impl<$($name_lt,)? K, V> From<alloc::collections::BTreeMap<K, V>> for $name $(<$name_lt>)?
+        where
+            K: Into<$str>,
+            V: Into<$name $(<$name_lt>)?>,
+        {
+            #[inline]
+            fn from(val: alloc::collections::BTreeMap<K, V>) -> $name $(<$name_lt>)? {
+                crate::value::misc::from_pairs(val)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<$($name_lt,)? K, V, H> From<std::collections::HashMap<K, V, H>> for $name $(<$name_lt>)?
+        where
+            K: Into<$str>,
+            V: Into<$name $(<$name_lt>)?>,
+        {
+            #[inline]
+            fn from(val: std::collections::HashMap<K, V, H>) -> $name $(<$name_lt>)? {
+                crate::value::misc::from_pairs(val)
+            }
+        }
+
+        crate::value::misc::from_number!(
+            [$($name_lt)?] $name $(<$name_lt>)?;
+            u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64
+        );
+
+        impl $(<$name_lt>)? crate::value::json::JsonValue for $name $(<$name_lt>)? {
+            type String = $str;
+
+            #[inline(always)]
+            fn null() -> Self {
+                $name::Null
+            }
+        }
+
         impl $(<$name_lt>)? Debug for $name $(<$name_lt>)? {
             fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
                 match self {
@@ -256,6 +319,27 @@ macro_rules! string_impl {
 
         impl<$lt> Eq for $type {}
 
+        impl<$lt> core::hash::Hash for $type {
+            #[inline]
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                core::hash::Hash::hash(self.as_str(), state)
+            }
+        }
+
+        impl<$lt> PartialOrd for $type {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<$lt> Ord for $type {
+            #[inline]
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.as_str().cmp(other.as_str())
+            }
+        }
+
         impl<$lt> core::ops::Deref for $type {
             type Target = str;
 
@@ -279,4 +363,38 @@ macro_rules! string_impl {
     };
 }
 
-pub(crate) use {define_value, string_impl};
+macro_rules! from_number {
+    ([$($gen:tt)*] $name:ty;) => {};
+
+    ([$($gen:tt)*] $name:ty; $type:ty $(, $rest:ty)*) => {
+        impl<$($gen)*> From<$type> for $name {
+            #[inline(always)]
+            fn from(val: $type) -> Self {
+                Self::Number(val.into())
+            }
+        }
+
+        crate::value::misc::from_number!([$($gen)*] $name; $($rest),*);
+    };
+}
+
+// appends the pairs as is, without checking for duplicate keys.
+pub(crate) fn from_pairs<I, K, V, T>(val: I) -> T
+where
+    I: IntoIterator<Item = (K, V), IntoIter: ExactSizeIterator>,
+    T: JsonValue + From<Object<T::String, T>>,
+    K: Into<T::String>,
+    V: Into<T>,
+{
+    let iter = val.into_iter();
+    let mut obj = Object::with_capacity(iter.len());
+
+    for (k, v) in iter {
+        obj.on_value(k.into(), v.into());
+    }
+
+    obj.on_complete();
+    obj.into()
+}
+
+pub(crate) use {define_value, from_number, string_impl};
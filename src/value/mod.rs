@@ -7,11 +7,15 @@ pub use number::Number;
 
 cfg_select! {
     feature = "alloc" => {
-        mod array;
+        pub mod array;
         pub mod borrowed;
+        #[cfg(feature = "hash-index")]
+        mod hash_index;
+        #[doc(hidden)]
+        pub mod json;
         pub mod lazy;
         mod misc;
-        mod object;
+        pub mod object;
         pub mod owned;
 
         pub use array::Array;
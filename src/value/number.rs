@@ -1,11 +1,23 @@
 use core::fmt::{Debug, Formatter, Result};
 
 /// Represents a JSON number.
+///
+/// A float number may be infinite or NaN when created through
+/// [`from_f64_lossless`](Self::from_f64_lossless), the `From` impls or parsing with
+/// [`Dialect::NON_FINITE`](crate::config::Dialect::NON_FINITE). Through serde, they become
+/// `null` in values and fail to deserialize into `Number`. NaN compares equal to itself.
+///
+/// ```
+/// use flexon::value::Number;
+///
+/// assert_eq!(Number::from(f64::NAN), Number::from(f64::NAN));
+/// assert_eq!(Number::from(0.0), Number::from(-0.0));
+/// ```
 #[repr(transparent)]
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Number(pub(crate) Kind);
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy)]
 pub enum Kind {
     Unsigned(u64),
     Signed(i64),
@@ -36,6 +48,15 @@ impl Number {
         }
     }
 
+    /// Creates JSON number from `f64`, keeping infinite and NaN values as is.
+    ///
+    /// Such numbers are serialized as `null` unless
+    /// [`Serializer::allow_non_finite`](crate::serde::Serializer::allow_non_finite) is set.
+    #[inline(always)]
+    pub fn from_f64_lossless(val: f64) -> Self {
+        Self(Kind::Float(val))
+    }
+
     /// Returns the number as `u64`, or `None` if it is not a positive integer.
     #[inline]
     pub fn as_u64(&self) -> Option<u64> {
@@ -80,10 +101,75 @@ impl Number {
     pub fn is_f64(&self) -> bool {
         self.as_f64().is_some()
     }
+
+    /// Returns `true` unless the number is infinite or NaN.
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        match self.0 {
+            Kind::Float(v) => v.is_finite(),
+            _ => true,
+        }
+    }
+}
+
+// NaN is equal to itself so that `Eq` stays reflexive, everything else
+// compares as the underlying primitives do.
+impl PartialEq for Kind {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Unsigned(a), Self::Unsigned(b)) => a == b,
+            (Self::Signed(a), Self::Signed(b)) => a == b,
+            (Self::Float(a), Self::Float(b)) => a == b || a.is_nan() && b.is_nan(),
+            _ => false,
+        }
+    }
 }
 
 impl Eq for Kind {}
 
+macro_rules! from_int {
+    ($($unsigned:ty),* ; $($signed:ty),*) => {
+        $(
+            impl From<$unsigned> for Number {
+                #[inline(always)]
+                fn from(val: $unsigned) -> Self {
+                    Self::from_u64(val as _)
+                }
+            }
+        )*
+
+        $(
+            // non negative integers are unsigned, same as the parsed ones.
+            impl From<$signed> for Number {
+                #[inline(always)]
+                fn from(val: $signed) -> Self {
+                    match val < 0 {
+                        true => Self::from_i64(val as _),
+                        _ => Self::from_u64(val as _),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_int!(u8, u16, u32, u64, usize; i8, i16, i32, i64, isize);
+
+impl From<f32> for Number {
+    #[inline(always)]
+    fn from(val: f32) -> Self {
+        Self::from_f64_lossless(val as _)
+    }
+}
+
+impl From<f64> for Number {
+    #[inline(always)]
+    fn from(val: f64) -> Self {
+        Self::from_f64_lossless(val)
+    }
+}
+
 impl Debug for Number {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self.0 {
@@ -1,93 +1,378 @@
+//! JSON object representation.
+
+#[cfg(feature = "hash-index")]
+use super::hash_index::HashIndex;
 use super::{
     Array,
+    array::IntoIter,
     builder::{ArrayBuilder, ObjectBuilder},
 };
 use core::{
     fmt::{Debug, Formatter, Result},
+    iter::Map,
+    mem::replace,
     ops::{Deref, Index, IndexMut},
+    slice::{Iter, IterMut},
 };
 
 /// Represents a JSON object.
 ///
 /// As of right now it stores elements as an array of key-value pairs. The original JSON source order is preserved.
-#[derive(Clone, PartialEq, Eq)]
-pub struct Object<K, V>(Array<(K, V)>);
+/// With the `hash-index` feature, large objects also keep a hash index of their keys so that lookups don't
+/// have to scan every pair. The hash is seeded per process with `std`, and objects whose keys collide too
+/// much fall back to scanning.
+#[derive(Clone)]
+pub struct Object<K, V> {
+    entries: Array<(K, V)>,
+    #[cfg(feature = "hash-index")]
+    index: HashIndex,
+}
 
 impl<K, V> Object<K, V> {
     /// Returns the object as a slice of key-value pairs.
     #[inline]
     pub fn as_slice(&self) -> &[(K, V)] {
-        &self.0
+        &self.entries
     }
 
     /// Returns the object as a mutable slice of key-value pairs.
+    ///
+    /// As the keys may be changed through it, the hash index is dropped until the next insertion.
     #[inline]
     pub fn as_slice_mut(&mut self) -> &mut [(K, V)] {
-        &mut self.0
+        #[cfg(feature = "hash-index")]
+        self.index.clear();
+        &mut self.entries
     }
 
     /// Returns the number of elements.
     #[inline]
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.entries.len()
+    }
+
+    /// Returns `true` if the object has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the key-value pairs, in order.
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over the key-value pairs with mutable values, in order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = (&K, &mut V)> + ExactSizeIterator {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over the keys, in order.
+    #[inline]
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Returns an iterator over the values, in order.
+    #[inline]
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator {
+        self.entries.iter().map(|(_, v)| v)
+    }
+
+    /// Returns an iterator over the mutable values, in order.
+    #[inline]
+    pub fn values_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut V> + ExactSizeIterator {
+        self.entries.iter_mut().map(|(_, v)| v)
     }
 }
 
 impl<K: Deref<Target = str>, V> Object<K, V> {
     /// Returns a reference to the value associated with the given key, `None` otherwise.
+    #[inline]
     pub fn get(&self, key: &str) -> Option<&V> {
-        for (k, v) in self.as_slice() {
-            if &**k == key {
-                return Some(v);
-            }
-        }
-
-        None
+        let idx = self.position(key)?;
+        Some(&self.entries[idx].1)
     }
 
     /// Returns a mutable reference to the value associated with the given key, `None` otherwise.
+    #[inline]
     pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
-        for (k, v) in self.as_slice_mut() {
-            if &**k == key {
-                return Some(v);
-            }
-        }
-
-        None
+        let idx = self.position(key)?;
+        Some(&mut self.entries[idx].1)
     }
 
     /// Returns a reference to the key-value pair associated with the given key, `None` otherwise.
+    #[inline]
     pub fn get_key_value(&self, key: &str) -> Option<&(K, V)> {
-        for v in self.as_slice() {
-            if &*v.0 == key {
-                return Some(v);
-            }
-        }
-
-        None
+        let idx = self.position(key)?;
+        Some(&self.entries[idx])
     }
 
     /// Returns a mutable reference to the key-value pair associated with the given key, `None` otherwise.
+    ///
+    /// As the key may be changed through it, the hash index is dropped until the next insertion.
+    #[inline]
     pub fn get_key_value_mut(&mut self, key: &str) -> Option<(&mut K, &mut V)> {
-        for (k, v) in self.as_slice_mut() {
-            if &**k == key {
-                return Some((k, v));
+        let idx = self.position(key)?;
+        let (k, v) = &mut self.as_slice_mut()[idx];
+        Some((k, v))
+    }
+
+    /// Inserts a key-value pair, replacing the value of the existing key if there is one.
+    ///
+    /// Returns the replaced value, `None` if the pair was appended to the end.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::Value;
+    ///
+    /// let mut val: Value = flexon::parse(r#"{"foo": 1, "bar": 2}"#)?;
+    /// let obj = val.as_object_mut().unwrap();
+    ///
+    /// assert!(obj.insert("foo".into(), Value::Null).is_some());
+    /// assert!(obj.insert("baz".into(), Value::Boolean(true)).is_none());
+    /// assert_eq!(obj.remove("bar").and_then(|v| v.as_u64()), Some(2));
+    /// assert!(obj.keys().map(|k| &**k).eq(["foo", "baz"]));
+    ///
+    /// # Ok::<_, flexon::Error>(())
+    /// ```
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        match self.position(&key) {
+            Some(idx) => Some(replace(&mut self.entries[idx].1, val)),
+            None => {
+                self.push(key, val);
+                None
             }
         }
+    }
+
+    /// Removes the given key and returns its value, `None` if it does not exist.
+    ///
+    /// The order of the rest of the pairs is preserved.
+    #[inline]
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        self.remove_entry(key).map(|v| v.1)
+    }
+
+    /// Removes the given key and returns the key-value pair, `None` if it does not exist.
+    ///
+    /// The order of the rest of the pairs is preserved.
+    #[inline]
+    pub fn remove_entry(&mut self, key: &str) -> Option<(K, V)> {
+        let idx = self.position(key)?;
+        let val = self.entries.remove(idx);
+        self.reindex();
+        Some(val)
+    }
+
+    /// Retains only the key-value pairs for which the given predicate returns `true`, preserving their order.
+    #[inline]
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        self.entries.retain(|(k, v)| f(k, v));
+        self.reindex();
+    }
+
+    /// Returns the entry of the given key for in-place manipulation.
+    ///
+    /// # Example
+    /// ```
+    /// use flexon::Value;
+    ///
+    /// let mut val: Value = flexon::parse(r#"{"debug": true}"#)?;
+    /// let obj = val.as_object_mut().unwrap();
+    ///
+    /// for key in ["debug", "verbose"] {
+    ///     obj.entry(key.into())
+    ///         .and_modify(|v| *v = Value::Boolean(false))
+    ///         .or_insert(Value::Boolean(true));
+    /// }
+    ///
+    /// assert_eq!(obj["debug"].as_bool(), Some(false));
+    /// assert_eq!(obj["verbose"].as_bool(), Some(true));
+    ///
+    /// # Ok::<_, flexon::Error>(())
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.position(&key) {
+            Some(idx) => Entry::Occupied(OccupiedEntry { obj: self, idx }),
+            None => Entry::Vacant(VacantEntry { obj: self, key }),
+        }
+    }
+
+    #[inline]
+    fn position(&self, key: &str) -> Option<usize> {
+        #[cfg(feature = "hash-index")]
+        if self.index.is_built() {
+            return self.index.find(&self.entries, key);
+        }
+
+        self.entries.iter().position(|(k, _)| &**k == key)
+    }
+
+    // appends the pair without checking for the key.
+    #[inline]
+    fn push(&mut self, key: K, val: V) {
+        self.entries.push((key, val));
+        #[cfg(feature = "hash-index")]
+        self.index.push(&self.entries);
+    }
+
+    // indices of the pairs have changed.
+    #[inline]
+    fn reindex(&mut self) {
+        #[cfg(feature = "hash-index")]
+        self.index.build(&self.entries);
+    }
+}
+
+/// A view into a single key of an [`Object`], either occupied or vacant.
+///
+/// Returned by [`Object::entry`].
+pub enum Entry<'a, K, V> {
+    /// The key exists in the object.
+    Occupied(OccupiedEntry<'a, K, V>),
+    /// The key does not exist in the object.
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// A key that exists in an [`Object`].
+pub struct OccupiedEntry<'a, K, V> {
+    obj: &'a mut Object<K, V>,
+    idx: usize,
+}
+
+/// A key that does not exist in an [`Object`].
+pub struct VacantEntry<'a, K, V> {
+    obj: &'a mut Object<K, V>,
+    key: K,
+}
+
+impl<'a, K: Deref<Target = str>, V> Entry<'a, K, V> {
+    /// Returns the key of the entry.
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(v) => v.key(),
+            Entry::Vacant(v) => v.key(),
+        }
+    }
+
+    /// Inserts the given value if the entry is vacant and returns a mutable reference to the value.
+    #[inline]
+    pub fn or_insert(self, val: V) -> &'a mut V {
+        self.or_insert_with(|| val)
+    }
+
+    /// Inserts the value returned by `f` if the entry is vacant and returns a mutable reference to the value.
+    #[inline]
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(v) => v.into_mut(),
+            Entry::Vacant(v) => v.insert(f()),
+        }
+    }
+
+    /// Inserts the default value if the entry is vacant and returns a mutable reference to the value.
+    #[inline]
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Calls `f` with the value if the entry is occupied.
+    #[inline]
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(v) = &mut self {
+            f(v.get_mut());
+        }
+
+        self
+    }
+}
+
+impl<'a, K: Deref<Target = str>, V> OccupiedEntry<'a, K, V> {
+    /// Returns the key of the entry.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.obj.entries[self.idx].0
+    }
+
+    /// Returns a reference to the value.
+    #[inline]
+    pub fn get(&self) -> &V {
+        &self.obj.entries[self.idx].1
+    }
+
+    /// Returns a mutable reference to the value.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.obj.entries[self.idx].1
+    }
+
+    /// Converts the entry into a mutable reference to the value.
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.obj.entries[self.idx].1
+    }
 
-        None
+    /// Replaces the value and returns the old one.
+    #[inline]
+    pub fn insert(&mut self, val: V) -> V {
+        replace(self.get_mut(), val)
+    }
+
+    /// Removes the key-value pair from the object and returns the value.
+    #[inline]
+    pub fn remove(self) -> V {
+        let (_, val) = self.obj.entries.remove(self.idx);
+        self.obj.reindex();
+        val
+    }
+}
+
+impl<'a, K: Deref<Target = str>, V> VacantEntry<'a, K, V> {
+    /// Returns the key of the entry.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes the ownership of the key.
+    #[inline]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Appends the key with the given value to the object and returns a mutable reference to the value.
+    #[inline]
+    pub fn insert(self, val: V) -> &'a mut V {
+        self.obj.push(self.key, val);
+        let idx = self.obj.len() - 1;
+        &mut self.obj.entries[idx].1
     }
 }
 
-impl<K, V> ObjectBuilder<K, V> for Object<K, V> {
+impl<K: Deref<Target = str>, V> ObjectBuilder<K, V> for Object<K, V> {
     #[inline]
     fn new() -> Self {
-        Self(Array::new())
+        Self {
+            entries: Array::new(),
+            #[cfg(feature = "hash-index")]
+            index: HashIndex::default(),
+        }
     }
 
     #[inline]
     fn with_capacity(cap: usize) -> Self {
-        Self(Array::with_capacity(cap))
+        Self {
+            entries: Array::with_capacity(cap),
+            #[cfg(feature = "hash-index")]
+            index: HashIndex::default(),
+        }
     }
 
     #[inline]
@@ -97,13 +382,85 @@ impl<K, V> ObjectBuilder<K, V> for Object<K, V> {
 
     #[inline]
     fn on_value(&mut self, key: K, val: V) {
-        self.0.on_value((key, val))
+        self.entries.on_value((key, val));
+        // indexed as it grows, so that duplicate key lookups while parsing don't scan.
+        #[cfg(feature = "hash-index")]
+        self.index.push(&self.entries);
+    }
+
+    #[inline]
+    fn find(&self, key: &K) -> Option<usize> {
+        self.position(key)
+    }
+
+    #[inline]
+    fn replace(&mut self, idx: usize, val: V) {
+        self.entries[idx].1 = val
     }
 
     #[inline]
     fn on_complete(&mut self) {}
 }
 
+impl<K: PartialEq, V: PartialEq> PartialEq for Object<K, V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for Object<K, V> {}
+
+impl<K: Deref<Target = str>, V> Extend<(K, V)> for Object<K, V> {
+    /// Inserts the key-value pairs as with [`Object::insert`].
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<K: Deref<Target = str>, V> FromIterator<(K, V)> for Object<K, V> {
+    /// Collects the key-value pairs as with [`Object::insert`].
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut obj = Self::new();
+        obj.extend(iter);
+        obj
+    }
+}
+
+impl<K, V> IntoIterator for Object<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<(K, V)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a Object<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Map<Iter<'a, (K, V)>, fn(&'a (K, V)) -> (&'a K, &'a V)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut Object<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = Map<IterMut<'a, (K, V)>, fn(&'a mut (K, V)) -> (&'a K, &'a mut V)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter_mut().map(|(k, v)| (&*k, v))
+    }
+}
+
 impl<K: Deref<Target = str>, V> Index<&str> for Object<K, V> {
     type Output = V;
 
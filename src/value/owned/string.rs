@@ -27,6 +27,11 @@ enum Inner {
 }
 
 impl String {
+    #[inline]
+    pub(crate) fn from_raw_parts(buf: *mut u8, len: usize, cap: usize) -> Self {
+        Self(Inner::Heap { buf, len, cap })
+    }
+
     /// Returns itself as a string slice.
     #[inline]
     pub fn as_str(&self) -> &str {
@@ -107,7 +112,7 @@ where
             unreachable_unchecked()
         };
 
-        if likely(*len == 0 && s.len() <= 30) {
+        if likely(*cap == 0 && s.len() <= 30) {
             let mut buf = [0; 30];
 
             buf.as_mut_ptr()